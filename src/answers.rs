@@ -0,0 +1,208 @@
+//! Accepted answers of every day, kept in `answers.toml` to catch refactors
+//! that change a result.
+//!
+//! ```toml
+//! [day01]
+//! part1 = 2815556
+//! part2 = 23927637
+//! ```
+
+use crate::summary::{PartReport, Status};
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Recorded answers keyed by day and part.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), usize>,
+}
+
+impl Answers {
+    /// Reads the answers in `path`; a missing file has no answers.
+    pub fn load(path: &str) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Result::Ok(text) => Answers::parse(&text).with_context(|| format!("invalid {}", path)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e).with_context(|| format!("cannot read {}", path)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let table = text.parse::<toml::Table>()?;
+
+        let mut answers = Answers::default();
+        for (section, parts) in table {
+            let day = section
+                .strip_prefix("day")
+                .and_then(|d| d.parse::<u8>().ok())
+                .with_context(|| format!("expected a `dayNN` section, found `{}`", section))?;
+            let parts = parts
+                .as_table()
+                .with_context(|| format!("`{}` is not a table", section))?;
+
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => bail!(
+                        "expected `part1` or `part2` in `{}`, found `{}`",
+                        section,
+                        key
+                    ),
+                };
+                let answer = value
+                    .as_integer()
+                    .and_then(|a| usize::try_from(a).ok())
+                    .with_context(|| format!("`{}.{}` is not an answer", section, key))?;
+                answers.set(day, part, answer);
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, self.to_string()).with_context(|| format!("cannot write {}", path))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<usize> {
+        self.answers.get(&(day, part)).copied()
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: usize) {
+        self.answers.insert((day, part), answer);
+    }
+
+    /// Stores the answers of all successful `reports`.
+    pub fn record(&mut self, reports: &[PartReport]) {
+        for report in reports {
+            if let (Status::Ok, Some(answer)) = (&report.status, report.answer) {
+                self.set(report.day, report.part, answer);
+            }
+        }
+    }
+
+    /// Compares `reports` with the recorded answers. Parts that were skipped
+    /// or have no recorded answer are not checked.
+    pub fn check(&self, reports: &[PartReport]) -> Vec<Difference> {
+        reports
+            .iter()
+            .filter(|r| r.status != Status::Skipped)
+            .filter_map(|r| {
+                let expected = self.get(r.day, r.part)?;
+                (r.answer != Some(expected)).then_some(Difference {
+                    day: r.day,
+                    part: r.part,
+                    expected,
+                    actual: r.answer,
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Accepted answers, checked by `aoc all --check`.")?;
+
+        let mut last_day = None;
+        for (&(day, part), answer) in &self.answers {
+            if last_day != Some(day) {
+                writeln!(f, "\n[day{:02}]", day)?;
+                last_day = Some(day);
+            }
+            writeln!(f, "part{} = {}", part, answer)?;
+        }
+        fmt::Result::Ok(())
+    }
+}
+
+/// An answer that does not match the recorded one.
+#[derive(Debug, PartialEq)]
+pub struct Difference {
+    pub day: u8,
+    pub part: u8,
+    pub expected: usize,
+    /// `None` if the part failed.
+    pub actual: Option<usize>,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {:02} part {}: -{}",
+            self.day, self.part, self.expected
+        )?;
+        match self.actual {
+            Some(actual) => write!(f, " +{}", actual),
+            None => write!(f, " (failed)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn report(day: u8, part: u8, answer: Option<usize>, status: Status) -> PartReport {
+        PartReport {
+            day,
+            part,
+            answer,
+            elapsed: Duration::ZERO,
+            status,
+        }
+    }
+
+    #[test]
+    fn answers_round_trip() -> Result<()> {
+        let mut answers = Answers::default();
+        answers.set(11, 2, 239413123020116);
+        answers.set(1, 1, 11);
+        answers.set(1, 2, 31);
+
+        let text = answers.to_string();
+        assert!(
+            text.contains("[day01]\npart1 = 11\npart2 = 31\n"),
+            "{}",
+            text
+        );
+        assert_eq!(answers, Answers::parse(&text)?);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_answers_are_rejected() {
+        assert!(Answers::parse("[day01]\npart3 = 1\n").is_err());
+        assert!(Answers::parse("[first]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day01]\npart1 = -1\n").is_err());
+    }
+
+    #[test]
+    fn changed_answers_are_reported() {
+        let mut answers = Answers::default();
+        answers.record(&[
+            report(1, 1, Some(11), Status::Ok),
+            report(1, 2, Some(31), Status::Ok),
+            report(2, 1, None, Status::Skipped),
+            report(2, 2, None, Status::Failed("oops".to_string())),
+        ]);
+        assert_eq!(None, answers.get(2, 2));
+
+        let reports = [
+            report(1, 1, Some(11), Status::Ok),
+            report(1, 2, Some(32), Status::Ok),
+            report(2, 2, Some(4), Status::Ok),
+        ];
+        let diff = answers.check(&reports);
+        assert_eq!(1, diff.len());
+        assert_eq!("day 01 part 2: -31 +32", diff[0].to_string());
+    }
+}
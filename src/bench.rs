@@ -0,0 +1,273 @@
+//! Repeated timing of the solutions, with a JSON baseline to spot
+//! performance regressions between runs.
+
+use crate::{solve_variant, Input, InputError, Solution, DEFAULT_VARIANT};
+use anyhow::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Untimed runs before the measurement.
+    pub warmup: usize,
+    /// Timed runs.
+    pub runs: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 3,
+            runs: 20,
+        }
+    }
+}
+
+/// Timing statistics of one part, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub day: u8,
+    pub part: u8,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub max_ns: u64,
+}
+
+impl BenchResult {
+    /// Summarises the timings in `samples`, which must not be empty.
+    pub fn from_samples(day: u8, part: u8, samples: &[Duration]) -> Self {
+        let mut ns = samples
+            .iter()
+            .map(|d| d.as_nanos() as u64)
+            .collect::<Vec<_>>();
+        ns.sort_unstable();
+
+        BenchResult {
+            day,
+            part,
+            runs: ns.len(),
+            min_ns: ns[0],
+            median_ns: percentile(&ns, 50),
+            p95_ns: percentile(&ns, 95),
+            max_ns: ns[ns.len() - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile `p` of the sorted `values`.
+fn percentile(values: &[u64], p: usize) -> u64 {
+    let rank = (p * values.len()).div_ceil(100).max(1);
+    values[rank - 1]
+}
+
+/// Runs one part of `solution` on `input` `options.runs` times after
+/// `options.warmup` untimed runs.
+pub fn bench_part(
+    solution: &dyn Solution,
+    part: u8,
+    input: &Input,
+    options: BenchOptions,
+) -> Result<BenchResult> {
+    bench_variant(solution, part, DEFAULT_VARIANT, input, options)
+}
+
+/// Like [`bench_part`] for the variant `name` of the part.
+pub fn bench_variant(
+    solution: &dyn Solution,
+    part: u8,
+    name: &str,
+    input: &Input,
+    options: BenchOptions,
+) -> Result<BenchResult> {
+    ensure!(options.runs > 0, "at least one run is needed");
+
+    let run = || {
+        let start = Instant::now();
+        solve_variant(solution, part, name, &mut input.reader())
+            .map_err(|e| InputError::attach_file(e, &input.name))?;
+        Ok(start.elapsed())
+    };
+
+    for _ in 0..options.warmup {
+        run()?;
+    }
+    let samples = (0..options.runs)
+        .map(|_| run())
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(BenchResult::from_samples(solution.day(), part, &samples))
+}
+
+pub fn save_baseline(path: &str, results: &[BenchResult]) -> Result<()> {
+    let json = serde_json::to_string_pretty(results)?;
+    fs::write(path, json + "\n").with_context(|| format!("cannot write {}", path))
+}
+
+pub fn load_baseline(path: &str) -> Result<Vec<BenchResult>> {
+    let json = fs::read_to_string(path).with_context(|| format!("cannot read {}", path))?;
+    serde_json::from_str(&json).with_context(|| format!("invalid baseline {}", path))
+}
+
+/// Median of a part compared with the baseline.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+    /// Relative change of the median in percent, positive if slower.
+    pub change: f64,
+    /// Whether `change` exceeds the threshold.
+    pub regression: bool,
+}
+
+/// Compares the medians of `results` with the ones in `baseline`, flagging
+/// parts that got slower by more than `threshold` percent. Parts missing
+/// from the baseline are left out.
+pub fn compare(
+    results: &[BenchResult],
+    baseline: &[BenchResult],
+    threshold: f64,
+) -> Vec<Comparison> {
+    results
+        .iter()
+        .filter_map(|r| {
+            let base = baseline
+                .iter()
+                .find(|b| b.day == r.day && b.part == r.part)?;
+            let change = (r.median_ns as f64 / base.median_ns.max(1) as f64 - 1.0) * 100.0;
+            Some(Comparison {
+                day: r.day,
+                part: r.part,
+                baseline_ns: base.median_ns,
+                current_ns: r.median_ns,
+                change,
+                regression: change > threshold,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn ns(ns: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns))
+}
+
+/// Renders `results` as a plain text table, one row per part.
+pub fn format_results(results: &[BenchResult]) -> String {
+    let mut table = String::new();
+    writeln!(
+        table,
+        "Day | Part | Runs | {:>10} | {:>10} | {:>10} | {:>10}",
+        "Min", "Median", "p95", "Max"
+    )
+    .unwrap();
+    writeln!(
+        table,
+        "----+------+------+{}",
+        ["------------"; 4].join("+")
+    )
+    .unwrap();
+
+    for r in results {
+        writeln!(
+            table,
+            " {:02} | {:>4} | {:>4} | {:>10} | {:>10} | {:>10} | {:>10}",
+            r.day,
+            r.part,
+            r.runs,
+            ns(r.min_ns),
+            ns(r.median_ns),
+            ns(r.p95_ns),
+            ns(r.max_ns)
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+/// Renders `comparisons` as a plain text table, one row per part.
+pub fn format_comparisons(comparisons: &[Comparison]) -> String {
+    let mut table = String::new();
+    writeln!(
+        table,
+        "Day | Part | {:>10} | {:>10} | {:>8} | Status",
+        "Baseline", "Median", "Change"
+    )
+    .unwrap();
+    writeln!(
+        table,
+        "----+------+------------+------------+----------+-------"
+    )
+    .unwrap();
+
+    for c in comparisons {
+        writeln!(
+            table,
+            " {:02} | {:>4} | {:>10} | {:>10} | {:>+7.1}% | {}",
+            c.day,
+            c.part,
+            ns(c.baseline_ns),
+            ns(c.current_ns),
+            c.change,
+            if c.regression { "REGRESSION" } else { "ok" }
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(day: u8, part: u8, median_ns: u64) -> BenchResult {
+        BenchResult {
+            day,
+            part,
+            runs: 1,
+            min_ns: median_ns,
+            median_ns,
+            p95_ns: median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn statistics_of_samples() {
+        let samples = (1..=20).rev().map(Duration::from_nanos).collect::<Vec<_>>();
+        let r = BenchResult::from_samples(1, 2, &samples);
+        assert_eq!(
+            (20, 1, 10, 19, 20),
+            (r.runs, r.min_ns, r.median_ns, r.p95_ns, r.max_ns)
+        );
+
+        let r = BenchResult::from_samples(1, 2, &[Duration::from_nanos(7)]);
+        assert_eq!((7, 7, 7, 7), (r.min_ns, r.median_ns, r.p95_ns, r.max_ns));
+    }
+
+    #[test]
+    fn baseline_round_trip() -> Result<()> {
+        let results = vec![result(1, 1, 1500), result(20, 2, 1_300_000_000)];
+        let json = serde_json::to_string(&results)?;
+        assert_eq!(results, serde_json::from_str::<Vec<BenchResult>>(&json)?);
+        Ok(())
+    }
+
+    #[test]
+    fn regressions_beyond_threshold() {
+        let baseline = [result(1, 1, 1000), result(1, 2, 1000), result(2, 1, 1000)];
+        let current = [result(1, 1, 1050), result(1, 2, 1200), result(11, 1, 5)];
+
+        let cmp = compare(&current, &baseline, 10.0);
+        assert_eq!(2, cmp.len());
+        assert!(!cmp[0].regression);
+        assert!(cmp[1].regression);
+        assert!((cmp[1].change - 20.0).abs() < 1e-9);
+    }
+}
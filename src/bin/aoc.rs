@@ -0,0 +1,619 @@
+use adv_code_2024::answers::{Answers, ANSWERS_FILE};
+use adv_code_2024::bench::{self, BenchOptions};
+use adv_code_2024::summary::{format_table, run_all, Status};
+use adv_code_2024::*;
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::Path;
+use std::str::FromStr;
+
+const USAGE: &str = "\
+usage: aoc <DAY> [--part <1|2>] [--input <FILE|->] [--record] [--variant <NAME>]
+       aoc <DAY> --compare [--part <1|2>] [--input <FILE|->] [--runs <N>]
+       aoc 20 --histogram [--input <FILE|->] [--radius <N>] [--min-saving <N>]
+       aoc 20 --cheats <FILE> [--input <FILE|->] [--radius <N>] [--min-saving <N>]
+       aoc all [--record | --check]
+       aoc new <DAY>
+       aoc render <DAY> [--input <FILE|->] [--output <FILE>] [--scale <N>]
+       aoc generate 20 --size <W>x<H> [--length <N>] [--seed <N>]
+                    [--output <FILE>] [--histogram [--radius <N>]]
+       aoc bench [<DAY>] [--part <1|2>] [--runs <N>] [--warmup <N>]
+                 [--save <FILE>] [--baseline <FILE>] [--threshold <PERCENT>]
+
+The input defaults to input/<DAY>.txt, or <DAY>.txt in $AOC_INPUT_DIR.
+--record saves the answers to answers.toml, --check compares every day
+with the answers saved there.
+--variant runs another registered implementation of the parts instead of
+the default one. --compare runs every variant, fails if their answers
+differ and compares their median times over --runs runs (default 1).
+--histogram prints the number of day 20 cheats of up to --radius
+picoseconds (default 2) per time saved, for the cheats saving at least
+--min-saving picoseconds (default 1). --cheats writes these cheats with
+their start, end, length and time saved to a .csv or .json file.
+new creates src/day<DAY>.rs from templates/NN.rs, its binary, input and
+example files, and registers the day in src/lib.rs.
+render draws the puzzle input of a day with its overlays in the terminal,
+or into a .ppm or .svg --output file with --scale pixels per cell
+(default 8).
+generate writes a random day 20 racetrack of the given odd size with a
+single path of --length picoseconds (default: as long as fits) from --seed
+(default 0). --histogram also prints the number of cheats of up to
+--radius picoseconds (default 2) per time saved, found by brute force.
+bench times every part --runs times (default 20) after --warmup untimed
+runs (default 3). --save writes the timings to a JSON file, --baseline
+compares them with a saved file and fails if a median got slower by more
+than --threshold percent (default 10).
+
+--threads sets the number of threads of the day 11 and day 20 solvers in
+a build with `--features parallel` (default: one per core).
+-v/--verbose prints debug output of the solvers to stderr, -vv also trace
+output. The level can be set with $AOC_LOG (error, warn, info, debug or
+trace) as well.
+Flags that a command does not use are rejected.";
+
+enum Command {
+    /// Run one or both parts of a single day.
+    Day {
+        day: u8,
+        part: Option<u8>,
+        input: Option<String>,
+        record: bool,
+        variant: Option<String>,
+    },
+    /// Print the day 20 cheats per saved time.
+    Histogram {
+        input: Option<String>,
+        radius: usize,
+        minimum_saving: usize,
+    },
+    /// Export the day 20 cheats.
+    Cheats {
+        input: Option<String>,
+        radius: usize,
+        minimum_saving: usize,
+        output: String,
+    },
+    /// Run every variant of a day and compare them.
+    Compare {
+        day: u8,
+        part: Option<u8>,
+        input: Option<String>,
+        options: BenchOptions,
+    },
+    /// Run every implemented day and print a summary table.
+    All { record: bool, check: bool },
+    /// Create a new day from the template.
+    New { day: u8 },
+    /// Draw the puzzle input of a day.
+    Render {
+        day: u8,
+        input: Option<String>,
+        output: Option<String>,
+        scale: usize,
+    },
+    /// Write a random puzzle input.
+    Generate {
+        options: day20::TrackOptions,
+        output: Option<String>,
+        histogram: Option<usize>,
+    },
+    /// Time one or every day.
+    Bench {
+        day: Option<u8>,
+        part: Option<u8>,
+        options: BenchOptions,
+        save: Option<String>,
+        baseline: Option<String>,
+        threshold: f64,
+    },
+}
+
+/// Parses the value following `flag`.
+fn value<T: FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<T> {
+    let value = args
+        .next()
+        .with_context(|| format!("{} needs a value\n\n{}", flag, USAGE))?;
+    value
+        .parse()
+        .map_err(|_| anyhow!("invalid value for {}: {}", flag, value))
+}
+
+/// Fails if a flag in `given` is not one of the `allowed` flags of `mode`.
+fn allow_flags(given: &[String], mode: &str, allowed: &[&str]) -> Result<()> {
+    match given.iter().find(|flag| !allowed.contains(&flag.as_str())) {
+        Some(flag) => bail!("{} cannot be used with {}\n\n{}", flag, mode, USAGE),
+        None => Ok(()),
+    }
+}
+
+fn parse_args() -> Result<Command> {
+    let mut mode = None;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut record = false;
+    let mut check = false;
+    let mut runs = None;
+    let mut warmup = None;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut verbosity = 0;
+    let mut threads = None;
+    let mut variant = None;
+    let mut compare = false;
+    let mut output = None;
+    let mut scale = 8;
+    let mut size = None;
+    let mut length = None;
+    let mut seed = 0;
+    let mut histogram = false;
+    let mut radius = 2;
+    let mut minimum_saving = 1;
+    let mut cheats = None;
+    // every flag but -v, by its long name
+    let mut given = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let flag = match arg.as_str() {
+            "-p" => "--part",
+            "-i" => "--input",
+            "-o" => "--output",
+            flag => flag,
+        };
+        if flag.starts_with("--") && flag != "--verbose" {
+            given.push(flag.to_string());
+        }
+        match arg.as_str() {
+            "-p" | "--part" => {
+                let value = value(&mut args, &arg)?;
+                ensure!(
+                    value == 1 || value == 2,
+                    "invalid value for {}: {}, the part must be 1 or 2",
+                    arg,
+                    value
+                );
+                part = Some(value);
+            }
+            "-i" | "--input" => input = Some(value(&mut args, &arg)?),
+            "--record" => record = true,
+            "--check" => check = true,
+            "--runs" => runs = Some(value(&mut args, &arg)?),
+            "--warmup" => warmup = Some(value(&mut args, &arg)?),
+            "--variant" => variant = Some(value(&mut args, &arg)?),
+            "--compare" => compare = true,
+            "--save" => save = Some(value(&mut args, &arg)?),
+            "--baseline" => baseline = Some(value(&mut args, &arg)?),
+            "--threshold" => threshold = value(&mut args, &arg)?,
+            "-o" | "--output" => output = Some(value(&mut args, &arg)?),
+            "--scale" => scale = value(&mut args, &arg)?,
+            "--size" => size = Some(value::<String>(&mut args, &arg)?),
+            "--length" => length = Some(value(&mut args, &arg)?),
+            "--seed" => seed = value(&mut args, &arg)?,
+            "--histogram" => histogram = true,
+            "--radius" => radius = value(&mut args, &arg)?,
+            "--min-saving" => minimum_saving = value(&mut args, &arg)?,
+            "--cheats" => cheats = Some(value(&mut args, &arg)?),
+            "--threads" => threads = Some(value(&mut args, &arg)?),
+            "-v" | "--verbose" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "all" | "bench" | "new" | "render" | "generate" if mode.is_none() => mode = Some(arg),
+            _ => {
+                day = Some(
+                    arg.parse()
+                        .with_context(|| format!("invalid day: {}\n\n{}", arg, USAGE))?,
+                )
+            }
+        }
+    }
+
+    if verbosity > 0 {
+        log::set_level(log::Level::from_verbosity(verbosity));
+    }
+    if let Some(threads) = threads {
+        parallel::set_threads(threads)?;
+    }
+
+    if mode.as_deref() == Some("bench") {
+        allow_flags(
+            &given,
+            "aoc bench",
+            &[
+                "--part",
+                "--runs",
+                "--warmup",
+                "--save",
+                "--baseline",
+                "--threshold",
+                "--threads",
+            ],
+        )?;
+        let defaults = BenchOptions::default();
+        return Ok(Command::Bench {
+            day,
+            part,
+            options: BenchOptions {
+                runs: runs.unwrap_or(defaults.runs),
+                warmup: warmup.unwrap_or(defaults.warmup),
+            },
+            save,
+            baseline,
+            threshold,
+        });
+    }
+
+    if mode.as_deref() == Some("new") {
+        allow_flags(&given, "aoc new", &[])?;
+        return Ok(Command::New {
+            day: day.context(USAGE)?,
+        });
+    }
+
+    if mode.as_deref() == Some("render") {
+        allow_flags(&given, "aoc render", &["--input", "--output", "--scale"])?;
+        return Ok(Command::Render {
+            day: day.context(USAGE)?,
+            input,
+            output,
+            scale,
+        });
+    }
+
+    if mode.as_deref() == Some("generate") {
+        ensure!(day == Some(20), "only day 20 inputs can be generated");
+        allow_flags(
+            &given,
+            "aoc generate",
+            &[
+                "--size",
+                "--length",
+                "--seed",
+                "--output",
+                "--histogram",
+                "--radius",
+            ],
+        )?;
+        let size = size.with_context(|| format!("generate needs --size\n\n{}", USAGE))?;
+        let (width, height) = size
+            .split_once('x')
+            .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+            .with_context(|| format!("invalid value for --size: {}", size))?;
+        return Ok(Command::Generate {
+            options: day20::TrackOptions {
+                width,
+                height,
+                length,
+                seed,
+            },
+            output,
+            histogram: histogram.then_some(radius),
+        });
+    }
+
+    if histogram && mode.is_none() {
+        ensure!(day == Some(20), "only day 20 has a cheat histogram");
+        ensure!(
+            cheats.is_none(),
+            "--histogram and --cheats cannot be combined\n\n{}",
+            USAGE
+        );
+        allow_flags(
+            &given,
+            "aoc 20 --histogram",
+            &[
+                "--histogram",
+                "--input",
+                "--radius",
+                "--min-saving",
+                "--threads",
+            ],
+        )?;
+        return Ok(Command::Histogram {
+            input,
+            radius,
+            minimum_saving,
+        });
+    }
+
+    if let Some(output) = cheats {
+        ensure!(day == Some(20), "only day 20 cheats can be exported");
+        ensure!(mode.is_none(), USAGE);
+        allow_flags(
+            &given,
+            "aoc 20 --cheats",
+            &[
+                "--cheats",
+                "--input",
+                "--radius",
+                "--min-saving",
+                "--threads",
+            ],
+        )?;
+        return Ok(Command::Cheats {
+            input,
+            radius,
+            minimum_saving,
+            output,
+        });
+    }
+
+    if compare {
+        ensure!(mode.is_none(), USAGE);
+        allow_flags(
+            &given,
+            "aoc <DAY> --compare",
+            &[
+                "--compare",
+                "--part",
+                "--input",
+                "--runs",
+                "--warmup",
+                "--threads",
+            ],
+        )?;
+        return Ok(Command::Compare {
+            day: day.context(USAGE)?,
+            part,
+            input,
+            options: BenchOptions {
+                runs: runs.unwrap_or(1),
+                warmup: warmup.unwrap_or(0),
+            },
+        });
+    }
+
+    if mode.is_some() || check {
+        ensure!(day.is_none(), USAGE);
+        allow_flags(&given, "aoc all", &["--record", "--check", "--threads"])?;
+        ensure!(!(record && check), USAGE);
+        return Ok(Command::All { record, check });
+    }
+
+    allow_flags(
+        &given,
+        "aoc <DAY>",
+        &["--part", "--input", "--record", "--variant", "--threads"],
+    )?;
+    Ok(Command::Day {
+        day: day.context(USAGE)?,
+        part,
+        input,
+        record,
+        variant,
+    })
+}
+
+fn find_solution(day: u8) -> Result<&'static dyn Solution> {
+    solution(day).with_context(|| format!("day {} is not implemented", day))
+}
+
+/// Prints the number of cheats per picoseconds saved.
+fn print_histogram(histogram: &BTreeMap<usize, usize>) {
+    println!("saving cheats");
+    for (saving, count) in histogram {
+        println!("{:>6} {:>6}", saving, count);
+    }
+    println!(" total {:>6}", histogram.values().sum::<usize>());
+}
+
+fn main() -> Result<()> {
+    match parse_args()? {
+        Command::Day {
+            day,
+            part,
+            input,
+            record,
+            variant,
+        } => {
+            let solution = find_solution(day)?;
+            let source = InputSource::resolve(day, input.as_deref());
+
+            let answers = match (part, variant) {
+                (Some(part), variant) => {
+                    let input = load_input(&source)?;
+                    start_day(&day.to_string());
+                    let name = variant.as_deref().unwrap_or(DEFAULT_VARIANT);
+                    vec![(part, run_variant(solution, part, name, &input)?)]
+                }
+                (None, Some(name)) => {
+                    // only the parts that have the variant
+                    let parts = (1..=2)
+                        .filter(|&p| variant_names(solution, p).contains(&name.as_str()))
+                        .collect::<Vec<_>>();
+                    ensure!(!parts.is_empty(), "day {} has no variant `{}`", day, name);
+                    let input = load_input(&source)?;
+                    start_day(&day.to_string());
+                    let mut answers = Vec::new();
+                    for part in parts {
+                        println!("=== Part {} ({}) ===", part, name);
+                        answers.push((part, run_variant(solution, part, &name, &input)?));
+                    }
+                    answers
+                }
+                (None, None) => {
+                    let [answer1, answer2] = run_day(solution, &source)?;
+                    vec![(1, answer1), (2, answer2)]
+                }
+            };
+
+            if record {
+                let mut recorded = Answers::load(ANSWERS_FILE)?;
+                for (part, answer) in answers {
+                    recorded.set(day, part, answer);
+                }
+                recorded.save(ANSWERS_FILE)?;
+            }
+            Ok(())
+        }
+        Command::Cheats {
+            input,
+            radius,
+            minimum_saving,
+            output,
+        } => {
+            let input = load_input(&InputSource::resolve(20, input.as_deref()))?;
+            let map = day20::parse_maze(input.reader())
+                .map_err(|e| InputError::attach_file(e, &input.name))?;
+            let times = day20::RaceTimes::new(&map)?;
+            let cheats = day20::maze_cheats(&times, Some(radius), Some(minimum_saving));
+            let n = day20::save_records(cheats, Path::new(&output))?;
+            println!("wrote {} cheats to {}", n, output);
+            Ok(())
+        }
+        Command::Compare {
+            day,
+            part,
+            input,
+            options,
+        } => {
+            let solution = find_solution(day)?;
+            let input = load_input(&InputSource::resolve(day, input.as_deref()))?;
+            let parts = part.map_or(vec![1, 2], |p| vec![p]);
+
+            let reports = compare::compare_variants(solution, &parts, &input, options)?;
+            print!("{}", compare::format_reports(&reports));
+
+            let diff = compare::disagreements(&reports);
+            if !diff.is_empty() {
+                println!("\nVariants disagreeing with the default:");
+                diff.iter().for_each(|d| println!("{}", d));
+            }
+            ensure!(diff.is_empty(), "{} variant(s) disagree", diff.len());
+            Ok(())
+        }
+        Command::All { record, check } => {
+            let reports = run_all();
+            print!("{}", format_table(&reports));
+
+            let failed = reports
+                .iter()
+                .filter(|r| matches!(r.status, Status::Failed(_)))
+                .count();
+
+            let mut recorded = Answers::load(ANSWERS_FILE)?;
+            if record {
+                recorded.record(&reports);
+                recorded.save(ANSWERS_FILE)?;
+            }
+            if check {
+                let diff = recorded.check(&reports);
+                if !diff.is_empty() {
+                    println!("\nAnswers differing from {}:", ANSWERS_FILE);
+                    diff.iter().for_each(|d| println!("{}", d));
+                }
+                ensure!(diff.is_empty(), "{} answer(s) changed", diff.len());
+            }
+
+            ensure!(failed == 0, "{} part(s) failed", failed);
+            Ok(())
+        }
+        Command::New { day } => {
+            for file in scaffold::new_day(Path::new("."), day)? {
+                println!("created {}", file.display());
+            }
+            println!("registered day{:02} in src/lib.rs", day);
+            Ok(())
+        }
+        Command::Render {
+            day,
+            input,
+            output,
+            scale,
+        } => {
+            let solution = find_solution(day)?;
+            let input = load_input(&InputSource::resolve(day, input.as_deref()))?;
+            let canvas = solution
+                .render(&mut input.reader())
+                .map_err(|e| InputError::attach_file(e, &input.name))?
+                .with_context(|| format!("day {} cannot be rendered", day))?;
+
+            match output {
+                Some(path) => canvas.save(Path::new(&path), scale)?,
+                None => print!("{}", canvas.to_ansi()),
+            }
+            Ok(())
+        }
+        Command::Generate {
+            options,
+            output,
+            histogram,
+        } => {
+            let map = day20::generate_track(&options)?;
+            match output {
+                Some(path) => std::fs::write(&path, map.to_string())
+                    .with_context(|| format!("cannot write {}", path))?,
+                None => print!("{}", map),
+            }
+            if let Some(radius) = histogram {
+                print_histogram(&day20::brute_force_histogram(&map, radius)?);
+            }
+            Ok(())
+        }
+        Command::Histogram {
+            input,
+            radius,
+            minimum_saving,
+        } => {
+            let input = load_input(&InputSource::resolve(20, input.as_deref()))?;
+            let histogram = day20::histogram(input.reader(), Some(radius), Some(minimum_saving))
+                .map_err(|e| InputError::attach_file(e, &input.name))?;
+            print_histogram(&histogram);
+            Ok(())
+        }
+        Command::Bench {
+            day,
+            part,
+            options,
+            save,
+            baseline,
+            threshold,
+        } => {
+            let solutions = match day {
+                Some(day) => vec![find_solution(day)?],
+                None => SOLUTIONS.to_vec(),
+            };
+            let parts = part.map_or(vec![1, 2], |p| vec![p]);
+
+            let mut results = Vec::new();
+            for solution in solutions {
+                let source = InputSource::resolve(solution.day(), None);
+                let input = match source.load() {
+                    Result::Ok(input) => input,
+                    // without an explicit day, days without input are skipped
+                    Err(_) if day.is_none() => continue,
+                    Err(e) => return Err(e).context(format!("cannot read {}", source.name())),
+                };
+                for &part in &parts {
+                    results.push(bench::bench_part(solution, part, &input, options)?);
+                }
+            }
+            print!("{}", bench::format_results(&results));
+
+            if let Some(path) = save {
+                bench::save_baseline(&path, &results)?;
+            }
+            if let Some(path) = baseline {
+                let comparisons =
+                    bench::compare(&results, &bench::load_baseline(&path)?, threshold);
+                println!("\nCompared with {}:", path);
+                print!("{}", bench::format_comparisons(&comparisons));
+
+                let regressions = comparisons.iter().filter(|c| c.regression).count();
+                ensure!(
+                    regressions == 0,
+                    "{} part(s) slower than {} by more than {}%",
+                    regressions,
+                    path,
+                    threshold
+                );
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Runs every variant of a day on the same input to check that they agree
+//! and to see which one is fastest.
+
+use crate::bench::{self, ns, BenchOptions, BenchResult};
+use crate::{solve_variant, variant_names, Input, InputError, Solution};
+use anyhow::*;
+use std::fmt::Write;
+
+/// Answer and timing of one variant of a part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantReport {
+    pub part: u8,
+    pub name: &'static str,
+    pub answer: usize,
+    pub timing: BenchResult,
+}
+
+/// Solves `parts` of `solution` on `input` with every variant, timing each
+/// one as [`bench::bench_variant`] does with `options`.
+pub fn compare_variants(
+    solution: &dyn Solution,
+    parts: &[u8],
+    input: &Input,
+    options: BenchOptions,
+) -> Result<Vec<VariantReport>> {
+    let mut reports = Vec::new();
+    for &part in parts {
+        for name in variant_names(solution, part) {
+            let answer = solve_variant(solution, part, name, &mut input.reader())
+                .map_err(|e| InputError::attach_file(e, &input.name))
+                .with_context(|| format!("variant `{}` of part {} failed", name, part))?;
+            let timing = bench::bench_variant(solution, part, name, input, options)?;
+            reports.push(VariantReport {
+                part,
+                name,
+                answer,
+                timing,
+            });
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Every variant whose answer differs from the default variant of its part,
+/// described for the user.
+pub fn disagreements(reports: &[VariantReport]) -> Vec<String> {
+    reports
+        .iter()
+        .filter_map(|r| {
+            let default = reports.iter().find(|d| d.part == r.part)?;
+            (r.answer != default.answer).then(|| {
+                format!(
+                    "part {}: `{}` answers {}, `{}` answers {}",
+                    r.part, r.name, r.answer, default.name, default.answer
+                )
+            })
+        })
+        .collect()
+}
+
+/// Renders `reports` as a plain text table, one row per variant, with the
+/// median time relative to the default variant of the part.
+pub fn format_reports(reports: &[VariantReport]) -> String {
+    let width = reports
+        .iter()
+        .map(|r| r.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    let mut table = String::new();
+    writeln!(
+        table,
+        "Part | {:<width$} | {:>15} | {:>10} | {:>8}",
+        "Variant", "Answer", "Median", "Relative"
+    )
+    .unwrap();
+    writeln!(
+        table,
+        "-----+-{}-+-----------------+------------+---------",
+        "-".repeat(width)
+    )
+    .unwrap();
+
+    for r in reports {
+        let default = reports.iter().find(|d| d.part == r.part).unwrap();
+        let relative = r.timing.median_ns as f64 / default.timing.median_ns.max(1) as f64;
+        writeln!(
+            table,
+            "{:>4} | {:<width$} | {:>15} | {:>10} | {:>7.2}x",
+            r.part,
+            r.name,
+            r.answer,
+            ns(r.timing.median_ns),
+            relative
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(part: u8, name: &'static str, answer: usize, median_ns: u64) -> VariantReport {
+        VariantReport {
+            part,
+            name,
+            answer,
+            timing: BenchResult {
+                day: 1,
+                part,
+                runs: 1,
+                min_ns: median_ns,
+                median_ns,
+                p95_ns: median_ns,
+                max_ns: median_ns,
+            },
+        }
+    }
+
+    #[test]
+    fn variants_of_day_11_agree() -> Result<()> {
+        let input = Input {
+            name: "example".to_string(),
+            text: "125 17\n".to_string(),
+        };
+        let options = BenchOptions { warmup: 0, runs: 1 };
+        let reports = compare_variants(&crate::day11::Day11, &[1], &input, options)?;
+        assert!(reports.len() > 1);
+        assert!(reports.iter().all(|r| r.part == 1 && r.answer == 55312));
+        assert!(disagreements(&reports).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn differing_answers_are_reported() {
+        let reports = [
+            report(1, "default", 4, 100),
+            report(1, "fast", 5, 50),
+            report(2, "default", 7, 10),
+        ];
+        assert_eq!(
+            vec!["part 1: `fast` answers 5, `default` answers 4"],
+            disagreements(&reports)
+        );
+
+        let table = format_reports(&reports);
+        assert_eq!(5, table.lines().count());
+        assert!(
+            table.contains("   1 | fast    |               5 |"),
+            "{}",
+            table
+        );
+        assert!(table.contains("0.50x"), "{}", table);
+    }
+}
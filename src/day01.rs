@@ -0,0 +1,120 @@
+use crate::{parse, Solution};
+use anyhow::*;
+use itertools::izip;
+use std::io::BufRead;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part1(reader)
+    }
+
+    fn part2(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part2(reader)
+    }
+}
+
+/// Parses the two location id columns of the puzzle input.
+pub fn split_lists<R: BufRead>(reader: R) -> Result<(Vec<isize>, Vec<isize>)> {
+    let [left, right] = parse::columns(reader)?;
+    Ok((left, right))
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<usize> {
+    let (col1, col2) = split_lists(reader)?;
+    Ok(total_distance(col1, col2))
+}
+
+/// Sum of the distances between the pairwise smallest ids of both lists.
+pub fn total_distance(mut col1: Vec<isize>, mut col2: Vec<isize>) -> usize {
+    // sort each vector
+    col1.sort();
+    col2.sort();
+
+    // get the elementwise total difference between the two vectors
+    if col1.len() != col2.len() {
+        panic!("Vectors are not the same length");
+    }
+
+    col1.iter()
+        .zip(col2.iter())
+        .map(|(a, b)| (a - b).unsigned_abs())
+        .sum()
+}
+
+pub fn part2<R: BufRead>(reader: R) -> Result<usize> {
+    let (col1, col2) = split_lists(reader)?;
+    Ok(similarity_score(col1, col2))
+}
+
+/// Sum of every id of the left list multiplied by its number of
+/// occurences in the right list.
+pub fn similarity_score(mut col1: Vec<isize>, mut col2: Vec<isize>) -> usize {
+    // sort each vector
+    col1.sort();
+    col2.sort();
+
+    // step 1: get unique elements of col1 and
+    //         the number of their occurences from col1
+    let mut col1_uni: Vec<isize> = Vec::new();
+    let mut col1_num: Vec<isize> = Vec::new();
+    col1.iter().for_each(|&x| {
+        if !col1_uni.contains(&x) {
+            col1_uni.push(x);
+            col1_num.push(1);
+        } else {
+            let len = col1_num.len() - 1;
+            col1_num[len] += 1;
+        }
+    });
+
+    // step 2: get the number of occurences of col1 elements in col2
+    let mut col2_num: Vec<isize> = Vec::new();
+    col1_uni.iter().for_each(|&x| {
+        let count2 = col2.iter().filter(|&y| *y == x).count();
+        col2_num.push(count2 as isize);
+    });
+
+    // step 3: get the similarity score as described in AoC
+    let zipped = izip!(col1_uni.iter(), col1_num.iter(), col2_num.iter());
+    let sim_score = zipped.map(|(u1, n1, n2)| u1 * n1 * n2).sum::<isize>();
+
+    sim_score as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputError;
+    use std::io::BufReader;
+
+    const TEST: &str = "\
+3  4
+4  3
+2  5
+1  3
+3  9
+3  3
+";
+
+    const EXPECTED1: usize = 11;
+    const EXPECTED2: usize = 31;
+
+    #[test]
+    fn malformed_line_is_reported() {
+        let err = split_lists(BufReader::new("3  4\n4 x3\n".as_bytes())).unwrap_err();
+        let err = err.downcast::<InputError>().unwrap();
+        assert_eq!((2, 3), (err.line, err.column));
+        assert_eq!("x3", err.text);
+    }
+
+    crate::examples! {
+        part1_example: part1(TEST) => EXPECTED1;
+        part2_example: part2(TEST) => EXPECTED2;
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{parse, Solution};
+use anyhow::*;
+use std::io::BufRead;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn part1(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part1(reader)
+    }
+
+    fn part2(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part2(reader)
+    }
+}
+
+/// Differences between consecutive levels.
+pub fn diff(telegram: &[isize]) -> Vec<isize> {
+    telegram
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .collect::<Vec<isize>>()
+}
+
+/// A report is safe if its levels strictly increase or decrease by 1 to 3.
+pub fn is_safe(telegram: &[isize]) -> Result<bool> {
+    let diffs = diff(telegram);
+    crate::trace!("{:?} - {:?}", telegram, diffs);
+
+    // check if all positive
+    let ascending = diffs.iter().all(|&d| d > 0);
+    // check if all negative
+    let descending = diffs.iter().all(|&d| d < 0);
+
+    // if neither ascending nor descending
+    if !ascending && !descending {
+        Ok(false)
+    } else {
+        // absolute differences squared
+        // squared differences are less than 9 -> abs dif < 3
+        let safe = diffs.iter().map(|&d| d * d).all(|d| d <= 9);
+        Ok(safe)
+    }
+}
+
+/// Meant to be [`is_safe`] tolerating a single bad level; not solved yet,
+/// every report counts as unsafe.
+fn is_safe_damped(telegram: &[isize]) -> Result<bool> {
+    let diffs = diff(telegram);
+    crate::trace!("{:?} - {:?}", telegram, diffs);
+
+    // check if all positive
+    // let ascending = diffs.iter().all(|&d| d > 0);
+    // check if all negative
+    // let descending = diffs.iter().all(|&d| d < 0);
+
+    Ok(false)
+}
+
+/// Parses one report of levels per line.
+pub fn parse_reports<R: BufRead>(reader: R) -> Result<Vec<Vec<isize>>> {
+    let mut reports = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        reports.push(parse::fields(i + 1, &line?)?);
+    }
+
+    Ok(reports)
+}
+
+/// Number of reports that pass the `safe` check.
+pub fn count_safe(
+    reports: &[Vec<isize>],
+    safe: impl Fn(&[isize]) -> Result<bool>,
+) -> Result<usize> {
+    let mut safe_count = 0;
+    for telegram in reports {
+        safe_count += safe(telegram)? as usize;
+    }
+
+    Ok(safe_count)
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<usize> {
+    count_safe(&parse_reports(reader)?, is_safe)
+}
+
+pub fn part2<R: BufRead>(reader: R) -> Result<usize> {
+    count_safe(&parse_reports(reader)?, is_safe_damped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputError;
+    use std::io::BufReader;
+
+    const TEST: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    const EXPECTED1: usize = 2;
+    const EXPECTED2: usize = 4;
+
+    #[test]
+    fn safe_reports() -> Result<()> {
+        assert!(is_safe(&[7, 6, 4, 2, 1])?);
+        assert!(!is_safe(&[1, 2, 7, 8, 9])?);
+        assert!(!is_safe(&[8, 6, 4, 4, 1])?);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_level_is_reported() {
+        let err = parse_reports(BufReader::new("7 6 4\n1 2 +x 8\n".as_bytes())).unwrap_err();
+        let err = err.downcast::<InputError>().unwrap();
+        assert_eq!((2, 5), (err.line, err.column));
+        assert_eq!("+x", err.text);
+    }
+
+    crate::examples! {
+        part1_example: part1(TEST) => EXPECTED1;
+        #[ignore = "part 2 is not solved yet"]
+        part2_example: part2(TEST) => EXPECTED2;
+    }
+}
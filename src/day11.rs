@@ -0,0 +1,253 @@
+use crate::memo::Memo;
+use crate::{parse, Solution, Variant};
+use anyhow::*;
+use std::io::BufRead;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn part1(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part1(reader)
+    }
+
+    fn part2(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part2(reader, 75)
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        &VARIANTS
+    }
+}
+
+const VARIANTS: [Variant; 2] = [
+    Variant {
+        part: 1,
+        name: "cached",
+        solve: |reader| part2(reader, 25),
+    },
+    Variant {
+        part: 2,
+        name: "cache-per-stone",
+        solve: |reader| count_stones(&parse_stones(reader)?, 75, split_stone_rec2),
+    },
+];
+
+/// Engraved number of `stone`; a stone that is no number is reported as
+/// the single line of its own input.
+fn stone_value(stone: &str) -> Result<usize> {
+    Ok(parse::token(1, stone, stone)?)
+}
+
+/// Number of stones `stone` turns into after `max_depth` blinks.
+pub fn split_stone_rec(stone: &str, max_depth: usize) -> Result<usize> {
+    if max_depth == 0 {
+        // break recursion
+        Ok(1)
+    } else {
+        // cut leading zeros
+        let val = stone_value(stone)?;
+        let _stone = val.to_string();
+        let l = _stone.len();
+        if _stone == "0" {
+            split_stone_rec("1", max_depth - 1)
+        } else if l % 2 == 0 {
+            let nstones = split_stone_rec(&_stone[..l / 2], max_depth - 1)?
+                + split_stone_rec(&_stone[l / 2..], max_depth - 1)?;
+            Ok(nstones)
+        } else {
+            split_stone_rec(&(val * 2024).to_string(), max_depth - 1)
+        }
+    }
+}
+
+/// [`split_stone_rec`] memoized by `(stone, remaining blinks)` in `memo`.
+pub fn split_stone_rec_cache(
+    stone: &str,
+    max_depth: usize,
+    memo: &mut Memo<(usize, usize), usize>,
+) -> Result<usize> {
+    let val = stone_value(stone)?;
+    if max_depth == 0 {
+        // break recursion
+        return Ok(1);
+    }
+
+    memo.try_call((val, max_depth), |memo| {
+        // cut leading zeros
+        let _stone = val.to_string();
+        let l = _stone.len();
+        if _stone == "0" {
+            split_stone_rec_cache("1", max_depth - 1, memo)
+        } else if l % 2 == 0 {
+            let nleft = split_stone_rec_cache(&_stone[..l / 2], max_depth - 1, memo)?;
+            let nright = split_stone_rec_cache(&_stone[l / 2..], max_depth - 1, memo)?;
+            Ok(nleft + nright)
+        } else {
+            split_stone_rec_cache(&(val * 2024).to_string(), max_depth - 1, memo)
+        }
+    })
+}
+
+/// [`split_stone_rec_cache`] with a fresh cache.
+pub fn split_stone_rec2(stone: &str, max_depth: usize) -> Result<usize> {
+    split_stone_rec_cache(stone, max_depth, &mut Memo::new())
+}
+
+/// Parses the engraved numbers of the stones in a row.
+pub fn parse_stones<R: BufRead>(reader: R) -> Result<Vec<String>> {
+    let mut stones = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let numbers = parse::fields::<usize>(i + 1, &line?)?;
+        stones.extend(numbers.iter().map(usize::to_string));
+    }
+
+    Ok(stones)
+}
+
+/// Total number of stones after blinking `max_depth` times at `stones`,
+/// with `split` counting the stones a single stone turns into.
+pub fn count_stones(
+    stones: &[String],
+    max_depth: usize,
+    mut split: impl FnMut(&str, usize) -> Result<usize>,
+) -> Result<usize> {
+    // recursively split stones
+    let mut nstones = 0;
+    for stone in stones.iter() {
+        nstones += split(stone, max_depth)?;
+    }
+
+    Ok(nstones)
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<usize> {
+    count_stones(&parse_stones(reader)?, 25, split_stone_rec)
+}
+
+pub fn part2<R: BufRead>(reader: R, max_depth: usize) -> Result<usize> {
+    let stones = parse_stones(reader)?;
+
+    // split every distinct stone once, spread over the workers; each worker keeps
+    // one cache for all the stones it splits
+    #[cfg(feature = "parallel")]
+    let nstones = {
+        use itertools::Itertools;
+        use rayon::prelude::*;
+        use std::sync::Mutex;
+        let memos = (0..rayon::current_num_threads())
+            .map(|_| Mutex::new(Memo::new()))
+            .collect::<Vec<_>>();
+        let nstones = stones
+            .iter()
+            .counts()
+            .into_par_iter()
+            .map(|(stone, n)| {
+                // a worker splits one stone at a time, so its cache is free
+                let worker = rayon::current_thread_index().unwrap_or(0);
+                let mut memo = memos[worker % memos.len()].lock().unwrap();
+                Ok(n * split_stone_rec_cache(stone, max_depth, &mut memo)?)
+            })
+            .try_reduce(|| 0, |a, b| Ok(a + b))?;
+        for (worker, memo) in memos.iter().enumerate() {
+            crate::debug!(
+                "day 11 cache of worker {}: {}",
+                worker,
+                memo.lock().unwrap().stats()
+            );
+        }
+        nstones
+    };
+
+    // one cache for all stones
+    #[cfg(not(feature = "parallel"))]
+    let nstones = {
+        let mut memo = Memo::new();
+        let nstones = count_stones(&stones, max_depth, |stone, depth| {
+            split_stone_rec_cache(stone, depth, &mut memo)
+        })?;
+        crate::debug!("day 11 cache: {}", memo.stats());
+        nstones
+    };
+
+    Ok(nstones)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputError;
+    use proptest::prelude::*;
+    use std::io::BufReader;
+
+    const TEST: &str = "\
+125 17
+";
+
+    const _TEST2: &str = "\
+125 17
+253000 1 7
+253 0 2024 14168
+512072 1 20 24 28676032
+512 72 2024 2 0 2 4 2867 6032
+1036288 7 2 20 24 4048 1 4048 8096 28 67 60 32
+2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2
+";
+
+    const EXPECTED1: usize = 55312;
+
+    #[test]
+    fn cached_split_matches_plain() -> Result<()> {
+        for stone in ["0", "1", "10", "125", "17", "2024"] {
+            for depth in 0..10 {
+                assert_eq!(
+                    split_stone_rec(stone, depth)?,
+                    split_stone_rec2(stone, depth)?
+                );
+            }
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn cached_split_matches_plain_on_random_stones(
+            stones in prop::collection::vec("[0-9]{1,7}", 1..6),
+            depth in 0usize..16,
+        ) {
+            let plain = count_stones(&stones, depth, split_stone_rec).unwrap();
+            let cached = count_stones(&stones, depth, split_stone_rec2).unwrap();
+            prop_assert_eq!(plain, cached);
+
+            // part 2 shares one cache between all stones
+            let shared = part2(stones.join(" ").as_bytes(), depth).unwrap();
+            prop_assert_eq!(plain, shared);
+        }
+    }
+
+    #[test]
+    fn malformed_single_stone_is_reported() {
+        for split in [split_stone_rec, split_stone_rec2] {
+            let err = split("12x", 3).unwrap_err();
+            let err = err.downcast::<InputError>().unwrap();
+            assert_eq!((1, 1), (err.line, err.column));
+            assert_eq!("12x", err.text);
+        }
+    }
+
+    #[test]
+    fn malformed_stone_is_reported() {
+        let err = parse_stones(BufReader::new("125 -17\n".as_bytes())).unwrap_err();
+        let err = err.downcast::<InputError>().unwrap();
+        assert_eq!((1, 5), (err.line, err.column));
+        assert_eq!("-17", err.text);
+    }
+
+    crate::examples! {
+        part1_example: part1(TEST) => EXPECTED1;
+        part2_example: part2(TEST, 25) => EXPECTED1;
+    }
+}
@@ -0,0 +1,1250 @@
+use crate::render::{Canvas, Rgb};
+use crate::{search, Grid, InputError, InputErrors, Point, Solution, Variant};
+use anyhow::*;
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn day(&self) -> u8 {
+        20
+    }
+
+    fn part1(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part1(reader, Some(100))
+    }
+
+    fn part2(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part2(reader, Some(20), Some(100))
+    }
+
+    fn render(&self, reader: &mut dyn BufRead) -> Result<Option<Canvas>> {
+        render(reader, Some(100)).map(Some)
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        &VARIANTS
+    }
+}
+
+const VARIANTS: [Variant; 5] = [
+    Variant {
+        part: 1,
+        name: "radius",
+        solve: |reader| part2(reader, Some(2), Some(100)),
+    },
+    Variant {
+        part: 1,
+        name: "diamond",
+        solve: |reader| count_cheats(reader, Some(2), Some(100), get_cheats_diamond),
+    },
+    Variant {
+        part: 2,
+        name: "diamond",
+        solve: |reader| count_cheats(reader, Some(20), Some(100), get_cheats_diamond),
+    },
+    Variant {
+        part: 1,
+        name: "maze",
+        solve: |reader| count_maze_cheats(reader, Some(2), Some(100)),
+    },
+    Variant {
+        part: 2,
+        name: "maze",
+        solve: |reader| count_maze_cheats(reader, Some(20), Some(100)),
+    },
+];
+
+/// One field of the racetrack map.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Cell {
+    Start,
+    End,
+    Wall,
+    Track,
+}
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cell::Start => write!(f, "S"),
+            Cell::End => write!(f, "E"),
+            Cell::Wall => write!(f, "#"),
+            Cell::Track => write!(f, "."),
+        }
+    }
+}
+/// A shortcut between two positions of the path, given as indices into it.
+#[derive(PartialEq, PartialOrd, Clone, Debug)]
+pub struct Cheat {
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Cheat {
+    /// Picoseconds saved by taking the cheat on `path`.
+    pub fn saving(&self, path: &[Point]) -> usize {
+        let cheat = path[self.from].manhattan(path[self.to]);
+        self.from.abs_diff(self.to).saturating_sub(cheat)
+    }
+}
+
+/// Everything about one cheat of [`maze_cheats`], for analysing them
+/// elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CheatRecord {
+    /// Last track cell before the cheat.
+    pub start: Point,
+    /// First track cell after the cheat.
+    pub end: Point,
+    /// Picoseconds spent cheating.
+    pub length: usize,
+    /// Picoseconds saved compared to the fastest honest race.
+    pub saved: usize,
+}
+
+/// Writes `records` as CSV, one line per cheat after a header, and
+/// returns how many there were.
+pub fn write_csv(
+    records: impl IntoIterator<Item = CheatRecord>,
+    mut out: impl Write,
+) -> Result<usize> {
+    writeln!(out, "start_row,start_col,end_row,end_col,length,saved")?;
+    let mut n = 0;
+    for r in records {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            r.start.row, r.start.col, r.end.row, r.end.col, r.length, r.saved
+        )?;
+        n += 1;
+    }
+    Ok(n)
+}
+
+/// Writes `records` as a JSON array of objects and returns how many there
+/// were.
+pub fn write_json(
+    records: impl IntoIterator<Item = CheatRecord>,
+    mut out: impl Write,
+) -> Result<usize> {
+    let mut n = 0;
+    let mut json = serde_json::Serializer::pretty(&mut out);
+    json.collect_seq(records.into_iter().inspect(|_| n += 1))?;
+    writeln!(out)?;
+    Ok(n)
+}
+
+/// Writes `records` to `path` as CSV or JSON, chosen by its extension, and
+/// returns how many there were.
+pub fn save_records(records: impl IntoIterator<Item = CheatRecord>, path: &Path) -> Result<usize> {
+    let file = || {
+        fs::File::create(path)
+            .map(io::BufWriter::new)
+            .with_context(|| format!("cannot write {}", path.display()))
+    };
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("csv") => write_csv(records, file()?),
+        Some("json") => write_json(records, file()?),
+        _ => bail!("{}: expected a .csv or .json file", path.display()),
+    }
+}
+
+/// Position of the first cell equal to `field`.
+pub fn search_field(map: &Grid<Cell>, field: &Cell) -> Result<Point> {
+    map.find(|cell| cell == field)
+        .with_context(|| format!("no `{}` on the map", field))
+}
+
+/// Shortest way from `start` to the end over the track, returning every
+/// position on it. On mazes with more than one way the cheats found along
+/// it miss those starting elsewhere, see [`maze_cheats`] for these.
+pub fn get_path(map: &Grid<Cell>, start: &Point) -> Result<Vec<Point>> {
+    let end = search_field(map, &Cell::End)?;
+    search::bfs_grid(map, *start, |cell| *cell != Cell::Wall)
+        .path_to(&end)
+        .context("no path to the end")
+}
+
+fn get_if_cheat(
+    xa: &usize,
+    ia: &usize,
+    xb: &usize,
+    ib: &usize,
+    cd: &usize,
+    cl: &usize,
+) -> Option<Cheat> {
+    let dy = *xa as isize - *xb as isize;
+    let dy2 = (dy * dy) as usize;
+
+    // the saved distance magnitude is 1 less,
+    // because of the travel through the wall
+    let mut saved_dist = *ia as isize - *ib as isize;
+    if saved_dist < 0 {
+        saved_dist += *cd as isize;
+    } else {
+        saved_dist -= *cd as isize;
+    }
+    let sd2 = (saved_dist * saved_dist) as usize;
+
+    if (dy2 == cd * cd) && (sd2 >= cl * cl) {
+        // push the indices of the tuple in path
+        return Some(Cheat { from: *ia, to: *ib });
+    }
+    None
+}
+
+/// Cheats that pass through exactly one wall and save at least `cheat_lim`
+/// picoseconds.
+pub fn get_cheats(
+    path: &[Point],
+    _map: &Grid<Cell>,
+    cheat_lim: Option<usize>,
+) -> Result<Vec<Cheat>> {
+    // path is a sequence of coordinates telling you the path
+    // from start to end,
+    // cheat_lim checks if the cheat saves enough pico seconds to be relevant
+    let cl = cheat_lim.unwrap_or(1);
+
+    let mut cheats = Vec::new();
+
+    // 1. lexicographic sort of path (order  x, y)
+    // 2. iter rows, find which Tracks are 2 fields appart and seperated by a wall
+    //    1. split into slices where row idx is equal
+    //    2. forward diff
+    //    3. filter for abs(diff) == 2 -> thes are the cheats in x-dir
+    // 3. check the length we could safe by using this cheat
+    //    1. get index of cheat.from and cheat.to in path
+    //    2. diff in indices is saved path length
+    // 4. filter for length >= cheat_lim
+    // 5. repeat for y (y, x)
+
+    const CHEAT_DIST: usize = 2;
+
+    // cheats in y direction
+    path.iter()
+        .enumerate()
+        .sorted_by_key(|(_, p)| (p.row, p.col))
+        .tuple_windows()
+        .filter(|((_, a), (_, b))| a.row == b.row)
+        .for_each(|((ia, a), (ib, b))| {
+            if let Some(v) = get_if_cheat(&a.col, &ia, &b.col, &ib, &CHEAT_DIST, &cl) {
+                cheats.push(v)
+            }
+        });
+
+    // cheats in x direction
+    path.iter()
+        .enumerate()
+        .sorted_by_key(|(_, p)| (p.col, p.row))
+        .tuple_windows()
+        .filter(|((_, a), (_, b))| a.col == b.col)
+        .for_each(|((ia, a), (ib, b))| {
+            if let Some(v) = get_if_cheat(&a.row, &ia, &b.row, &ib, &CHEAT_DIST, &cl) {
+                cheats.push(v)
+            }
+        });
+
+    Result::Ok(cheats)
+}
+
+/// Cheats of up to `rad` picoseconds that save at least `minimum_saving`
+/// picoseconds.
+pub fn get_cheats_rad(
+    path: &[Point],
+    _map: &Grid<Cell>,
+    rad: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> Result<Vec<Cheat>> {
+    // path is a sequence of coordinates telling
+    // you the track positions in map from start to end,
+    // rad checks possible track positons reachable from
+    // the current position
+    // minimum_saving filters for the mimimum distance
+    // a cheat must save to be relevant
+    let r = rad.unwrap_or(1);
+    let mins = minimum_saving.unwrap_or(0);
+
+    Ok(collect_cheats(path.len(), |fi| {
+        cheats_from(path, fi, r, mins)
+    }))
+}
+
+/// The cheats `cheats_from` finds for every start index below `len`, in
+/// order of their start.
+fn collect_cheats<I>(len: usize, cheats_from: impl Fn(usize) -> I + Send + Sync) -> Vec<Cheat>
+where
+    I: Iterator<Item = Cheat>,
+{
+    // every start of a cheat is independent of the others
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        (0..len)
+            .into_par_iter()
+            .flat_map_iter(cheats_from)
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..len).flat_map(cheats_from).collect()
+    }
+}
+
+/// Cheats of [`get_cheats_rad`] starting at `path[fi]`.
+fn cheats_from(
+    path: &[Point],
+    fi: usize,
+    r: usize,
+    mins: usize,
+) -> impl Iterator<Item = Cheat> + '_ {
+    let r2 = r * r;
+    let mins2 = mins * mins;
+    let from = path[fi];
+    // possible cheats must be within a window of +-r
+    // around the current coordinate
+    path.iter().enumerate().filter_map(move |(ti, to)| {
+        // manhattan distance
+        let md = from.manhattan(*to) as isize;
+        let md2 = (md * md) as usize;
+        // coord should be reachable within r
+        if md2 > r2 {
+            return None;
+        }
+        // saved distance is distance in path index +- the
+        // cheat distance
+        let mut saved_dist = ti as isize - fi as isize;
+        if saved_dist < 0 {
+            saved_dist += md;
+        } else {
+            saved_dist -= md;
+        }
+        let sd2 = (saved_dist * saved_dist) as usize;
+
+        // check path direction and minimum saved distance
+        if fi < ti && sd2 >= mins2 {
+            crate::trace!("cheat {} -> {} saves {}", from, to, saved_dist);
+            Some(Cheat { from: fi, to: ti })
+        } else {
+            None
+        }
+    })
+}
+
+/// Same cheats as [`get_cheats_rad`], but instead of comparing every pair
+/// of track cells only the cells within `rad` of a cheat start are looked
+/// up in a map of the path index per cell. Takes O(path × rad²) time instead
+/// of O(path²); the cheats of one start come in another order.
+pub fn get_cheats_diamond(
+    path: &[Point],
+    map: &Grid<Cell>,
+    rad: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> Result<Vec<Cheat>> {
+    let r = rad.unwrap_or(1);
+    let mins = minimum_saving.unwrap_or(0);
+
+    let mut index = Grid::filled(map.width(), map.height(), None);
+    for (i, p) in path.iter().enumerate() {
+        *index.get_mut(*p).context("path leaves the map")? = Some(i);
+    }
+
+    let cheats_from = |fi: usize| {
+        let from = path[fi];
+        let index = &index;
+        from.within_manhattan(r).filter_map(move |to| {
+            let ti = (*index.get(to)?)?;
+            (fi < ti && ti - fi >= from.manhattan(to) + mins).then_some(Cheat { from: fi, to: ti })
+        })
+    };
+
+    Ok(collect_cheats(path.len(), cheats_from))
+}
+
+/// Fastest honest race times over every cell of a maze, which unlike the
+/// puzzle inputs may branch, loop and have dead ends.
+#[derive(Debug, Clone)]
+pub struct RaceTimes {
+    /// Picoseconds from `S` to every reachable cell.
+    pub from_start: Grid<Option<usize>>,
+    /// Picoseconds from every cell that can reach `E` to `E`.
+    pub to_end: Grid<Option<usize>>,
+    /// Picoseconds of the fastest race without cheating.
+    pub best: usize,
+}
+
+impl RaceTimes {
+    pub fn new(map: &Grid<Cell>) -> Result<Self> {
+        let start = search_field(map, &Cell::Start)?;
+        let end = search_field(map, &Cell::End)?;
+        let open = |cell: &Cell| *cell != Cell::Wall;
+        let from_start = search::distance_grid(map, start, open);
+        let to_end = search::distance_grid(map, end, open);
+        let best = from_start[end].context("no path to the end")?;
+
+        Ok(RaceTimes {
+            from_start,
+            to_end,
+            best,
+        })
+    }
+
+    /// Picoseconds saved by a race that cheats from `a` to `b`, if it is
+    /// faster than the best honest one.
+    pub fn saving(&self, a: Point, b: Point) -> Option<usize> {
+        let cheat = a.manhattan(b);
+        let time = self.from_start[a]? + cheat + self.to_end[b]?;
+        self.best.checked_sub(time).filter(|&saving| saving > 0)
+    }
+}
+
+/// Every cheat of up to `radius` picoseconds on any maze that saves at
+/// least `minimum_saving` picoseconds, given the race `times` of the maze.
+/// Each cell reachable from `S` is a cheat start, and the cells within
+/// `radius` of it that reach `E` are its ends, so every pair of positions
+/// comes up once.
+pub fn maze_cheats(
+    times: &RaceTimes,
+    radius: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> impl Iterator<Item = CheatRecord> + '_ {
+    let r = radius.unwrap_or(1);
+    let mins = minimum_saving.unwrap_or(1);
+
+    times.from_start.positions().flat_map(move |a| {
+        a.within_manhattan(r)
+            .filter(|&b| times.from_start.contains(b))
+            .filter_map(move |b| {
+                let saved = times.saving(a, b).filter(|&s| s >= mins)?;
+                Some(CheatRecord {
+                    start: a,
+                    end: b,
+                    length: a.manhattan(b),
+                    saved,
+                })
+            })
+    })
+}
+
+/// Number of cheats per saved time of [`maze_cheats`].
+pub fn maze_histogram(
+    map: &Grid<Cell>,
+    radius: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> Result<BTreeMap<usize, usize>> {
+    let times = RaceTimes::new(map)?;
+    Ok(maze_cheats(&times, radius, minimum_saving)
+        .map(|record| record.saved)
+        .counts()
+        .into_iter()
+        .collect())
+}
+
+/// Number of cheats on the maze in `reader`, see [`maze_cheats`].
+pub fn count_maze_cheats<R: BufRead>(
+    reader: R,
+    radius: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> Result<usize> {
+    let times = RaceTimes::new(&parse_maze(reader)?)?;
+    Ok(maze_cheats(&times, radius, minimum_saving).count())
+}
+
+/// Number of cheats of up to `radius` picoseconds per saved time, for the
+/// cheats saving at least `minimum_saving` picoseconds.
+pub fn cheat_histogram(
+    path: &[Point],
+    map: &Grid<Cell>,
+    radius: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> Result<BTreeMap<usize, usize>> {
+    // a cheat must save something to count
+    let minimum_saving = minimum_saving.unwrap_or(1).max(1);
+    let cheats = get_cheats_rad(path, map, radius, Some(minimum_saving))?;
+    let mut histogram = BTreeMap::new();
+    for cheat in cheats {
+        *histogram.entry(cheat.saving(path)).or_insert(0) += 1;
+    }
+
+    Ok(histogram)
+}
+
+/// Parses the racetrack map, one row of cells per line, and checks it with
+/// [`map_problems`]. Unknown characters and rows of different lengths are
+/// reported by [`Grid::parse`] already.
+pub fn parse_map<R: BufRead>(reader: R) -> Result<Grid<Cell>> {
+    let map = parse_cells(reader)?;
+    let errors = map_problems(&map);
+    if !errors.is_empty() {
+        return Err(errors.into());
+    }
+    Ok(map)
+}
+
+/// Parses a maze like [`parse_map`], but only checks it with
+/// [`maze_problems`]: it may branch, loop, have dead ends and parts that
+/// cannot be reached.
+pub fn parse_maze<R: BufRead>(reader: R) -> Result<Grid<Cell>> {
+    let map = parse_cells(reader)?;
+    let errors = maze_problems(&map);
+    if !errors.is_empty() {
+        return Err(errors.into());
+    }
+    Ok(map)
+}
+
+fn parse_cells<R: BufRead>(reader: R) -> Result<Grid<Cell>> {
+    Grid::parse(reader, |c| match c {
+        'S' => Some(Cell::Start),
+        'E' => Some(Cell::End),
+        '#' => Some(Cell::Wall),
+        '.' => Some(Cell::Track),
+        _ => None,
+    })
+}
+
+/// Everything wrong with `map` as a racetrack: on top of the rules of
+/// [`maze_problems`] every track cell must be on the path from `S` to `E`,
+/// so the track has no branches, dead ends or loops and the solvers
+/// following the path see every cheat.
+pub fn map_problems(map: &Grid<Cell>) -> InputErrors {
+    let mut errors = maze_problems(map);
+    errors.summary = "invalid racetrack map".to_string();
+    let start = map.find(|cell| *cell == Cell::Start);
+    let end = map.find(|cell| *cell == Cell::End);
+    let open = |cell: &Cell| *cell != Cell::Wall;
+    let Some(path) = start
+        .zip(end)
+        .and_then(|(start, end)| search::bfs_grid(map, start, open).path_to(&end))
+    else {
+        return errors;
+    };
+
+    // report every part of the track off the path once
+    let mut off_path = map.map(open);
+    for &pos in &path {
+        off_path[pos] = false;
+    }
+    for pos in map.positions() {
+        if !off_path[pos] {
+            continue;
+        }
+        let part = search::bfs_grid(&off_path, pos, |&off| off);
+        for p in part.dist.keys() {
+            off_path[*p] = false;
+        }
+        let reason = format!(
+            "{} track cell(s) off the path from `S` to `E`",
+            part.dist.len()
+        );
+        errors.problems.push(InputError::new(
+            pos.row + 1,
+            pos.col + 1,
+            &map[pos].to_string(),
+            reason,
+        ));
+    }
+
+    errors
+}
+
+/// Everything wrong with `map` as a maze: it must have exactly one `S` and
+/// one `E`, walls all around, and `E` must be reachable from `S`. Problems
+/// of a cell are reported at its row and column, a missing `S` or `E` for
+/// the whole map.
+pub fn maze_problems(map: &Grid<Cell>) -> InputErrors {
+    let mut errors = shape_problems(map, "invalid maze");
+    let start = map.find(|cell| *cell == Cell::Start);
+    let end = map.find(|cell| *cell == Cell::End);
+    if let (Some(start), Some(end)) = (start, end) {
+        let from_start = search::distance_grid(map, start, |cell| *cell != Cell::Wall);
+        if from_start[end].is_none() {
+            let reason = "`E` cannot be reached from `S`".to_string();
+            errors
+                .problems
+                .push(InputError::new(end.row + 1, end.col + 1, "E", reason));
+        }
+    }
+
+    errors
+}
+
+/// Problems of `map` that make it no racetrack at all, summed up as
+/// `summary`: it must have exactly one `S` and one `E` and walls all around.
+/// Problems of a cell are reported at its row and column, a missing `S` or
+/// `E` for the whole map.
+fn shape_problems(map: &Grid<Cell>, summary: &str) -> InputErrors {
+    let mut errors = InputErrors::new(summary);
+    let problem = |pos: Point, reason: String| {
+        InputError::new(pos.row + 1, pos.col + 1, &map[pos].to_string(), reason)
+    };
+    if map.width() == 0 || map.height() == 0 {
+        errors.general.push("the map is empty".to_string());
+        return errors;
+    }
+
+    for field in [Cell::Start, Cell::End] {
+        match map.find_all(|cell| *cell == field)[..] {
+            [] => errors.general.push(format!("no `{}` on the map", field)),
+            [_] => {}
+            [first, ref others @ ..] => {
+                for &pos in others {
+                    let reason = format!(
+                        "another `{}`, the first one is at {}:{}",
+                        field,
+                        first.row + 1,
+                        first.col + 1
+                    );
+                    errors.problems.push(problem(pos, reason));
+                }
+            }
+        }
+    }
+
+    let (w, h) = (map.width(), map.height());
+    let open = |cell: &Cell| *cell != Cell::Wall;
+    for (pos, cell) in map.iter() {
+        let border = pos.row == 0 || pos.col == 0 || pos.row == h - 1 || pos.col == w - 1;
+        if border && open(cell) {
+            errors.problems.push(problem(
+                pos,
+                "the map must be enclosed by walls".to_string(),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// The track as a heat map of the time from the start, with the cheats of
+/// part 1 saving at least `cheat_lim` picoseconds drawn on top.
+pub fn render<R: BufRead>(reader: R, cheat_lim: Option<usize>) -> Result<Canvas> {
+    let map = parse_map(reader)?;
+    let start = search_field(&map, &Cell::Start)?;
+    let path = get_path(&map, &start)?;
+    let cheats = get_cheats(&path, &map, cheat_lim)?;
+
+    let mut time = Grid::filled(map.width(), map.height(), None);
+    for (i, &p) in path.iter().enumerate() {
+        time[p] = Some(i);
+    }
+
+    let canvas = Canvas::from_grid(&map, |cell| match cell {
+        Cell::Wall => Rgb::GREY,
+        _ => Rgb::BLACK,
+    });
+    Ok(canvas.heat_map(&time).pairs(
+        cheats.iter().map(|c| {
+            // in race order, from the earlier to the later position
+            let (from, to) = (c.from.min(c.to), c.from.max(c.to));
+            (path[from], path[to])
+        }),
+        Rgb::WHITE,
+        Rgb::BLACK,
+    ))
+}
+
+pub fn part1<R: BufRead>(reader: R, cheat_lim: Option<usize>) -> Result<usize> {
+    let map = parse_map(reader)?;
+
+    // get picoseconds a cheat must save to get captured
+    let cl = cheat_lim.unwrap_or(1);
+
+    // get start location
+    let start = search_field(&map, &Cell::Start)?;
+    crate::debug!("start: {}", start);
+
+    // find the path
+    let path = get_path(&map, &start)?;
+    let cheats = get_cheats(&path, &map, Some(cl))?;
+
+    Ok(cheats.len())
+}
+
+/// [`maze_histogram`] of the maze in `reader`, counting the same cheats
+/// [`maze_cheats`] yields.
+pub fn histogram<R: BufRead>(
+    reader: R,
+    radius: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> Result<BTreeMap<usize, usize>> {
+    maze_histogram(&parse_maze(reader)?, radius, minimum_saving)
+}
+
+pub fn part2<R: BufRead>(
+    reader: R,
+    radius: Option<usize>,
+    minimum_saving: Option<usize>,
+) -> Result<usize> {
+    count_cheats(reader, radius, minimum_saving, get_cheats_rad)
+}
+
+/// A cheat search like [`get_cheats_rad`], taking the path, the map, the
+/// cheat radius and the minimum saving.
+pub type CheatSearch =
+    fn(&[Point], &Grid<Cell>, Option<usize>, Option<usize>) -> Result<Vec<Cheat>>;
+
+/// Number of cheats `search` finds on the racetrack in `reader`.
+pub fn count_cheats<R: BufRead>(
+    reader: R,
+    radius: Option<usize>,
+    minimum_saving: Option<usize>,
+    search: CheatSearch,
+) -> Result<usize> {
+    let map = parse_map(reader)?;
+
+    // get start and path
+    let start = search_field(&map, &Cell::Start)?;
+    let path = get_path(&map, &start)?;
+
+    // find the cheats
+    let cheats = search(&path, &map, radius, minimum_saving)?;
+
+    Ok(cheats.len())
+}
+
+/// Size and seed of a generated racetrack.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackOptions {
+    /// Columns of the map, odd.
+    pub width: usize,
+    /// Rows of the map, odd.
+    pub height: usize,
+    /// Picoseconds of the honest race, even; the longest track that fits if
+    /// `None`.
+    pub length: Option<usize>,
+    pub seed: u64,
+}
+
+/// SplitMix64, enough to make the generated tracks reproducible.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Generates a racetrack with a single path from `S` to `E`.
+///
+/// Like the puzzle inputs, the track runs over the cells with odd
+/// coordinates and the cells between them. It is a prefix of a random
+/// Hamiltonian path over these cells, shuffled with backbite moves starting
+/// from a serpentine.
+pub fn generate_track(options: &TrackOptions) -> Result<Grid<Cell>> {
+    let TrackOptions {
+        width,
+        height,
+        length,
+        seed,
+    } = *options;
+    ensure!(
+        width % 2 == 1 && height % 2 == 1,
+        "the map size must be odd, got {}x{}",
+        width,
+        height
+    );
+    let (rows, cols) = (height / 2, width / 2);
+    let max_length = 2 * (rows * cols).saturating_sub(1);
+    let length = length.unwrap_or(max_length);
+    ensure!(
+        length >= 2 && length <= max_length && length % 2 == 0,
+        "the track length must be even and between 2 and {} for {}x{}, got {}",
+        max_length,
+        width,
+        height,
+        length
+    );
+
+    let mut rng = SplitMix64(seed);
+    let mut path = (0..rows)
+        .flat_map(|r| {
+            (0..cols).map(move |c| Point::new(r, if r % 2 == 0 { c } else { cols - 1 - c }))
+        })
+        .collect::<Vec<_>>();
+    let mut index = Grid::filled(cols, rows, 0);
+    let reindex = |path: &[Point], index: &mut Grid<usize>, from: usize| {
+        for (i, &p) in path.iter().enumerate().skip(from) {
+            index[p] = i;
+        }
+    };
+    reindex(&path, &mut index, 0);
+
+    for _ in 0..10 * path.len() {
+        if rng.below(2) == 0 {
+            // move the other end next time
+            path.reverse();
+            reindex(&path, &mut index, 0);
+        }
+        // connect the end to one of its neighbours and reverse the loop
+        // this closes, the path still visits every cell
+        let end = *path.last().unwrap();
+        let neighbours = index.neighbours4(end).collect::<Vec<_>>();
+        let i = index[neighbours[rng.below(neighbours.len())]];
+        if i + 2 < path.len() {
+            path[i + 1..].reverse();
+            reindex(&path, &mut index, i + 1);
+        }
+    }
+
+    let mut map = Grid::filled(width, height, Cell::Wall);
+    let cell = |p: Point| Point::new(2 * p.row + 1, 2 * p.col + 1);
+    let track = &path[..length / 2 + 1];
+    for (&a, &b) in track.iter().tuple_windows() {
+        map[cell(a)] = Cell::Track;
+        map[Point::new(a.row + b.row + 1, a.col + b.col + 1)] = Cell::Track;
+        map[cell(b)] = Cell::Track;
+    }
+    map[cell(track[0])] = Cell::Start;
+    map[cell(track[track.len() - 1])] = Cell::End;
+
+    Ok(map)
+}
+
+/// Number of cheats of up to `radius` picoseconds per saved time, found by
+/// trying every pair of track cells. Works for any maze, but takes
+/// quadratic time in the number of track cells.
+pub fn brute_force_histogram(map: &Grid<Cell>, radius: usize) -> Result<BTreeMap<usize, usize>> {
+    let times = RaceTimes::new(map)?;
+    let track = map.find_all(|cell| *cell != Cell::Wall);
+    let mut histogram = BTreeMap::new();
+    for &a in &track {
+        for &b in &track {
+            if a.manhattan(b) > radius {
+                continue;
+            }
+            if let Some(saving) = times.saving(a, b) {
+                *histogram.entry(saving).or_insert(0) += 1;
+            }
+        }
+    }
+
+    Ok(histogram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::io::BufReader;
+
+    const TEST: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    const EXPECTED1: usize = 44;
+
+    /// Cheats of the example saving at least one picosecond in part 1.
+    const EXPECTED1_SAVINGS: [(usize, usize); 11] = [
+        (2, 14),
+        (4, 14),
+        (6, 2),
+        (8, 4),
+        (10, 2),
+        (12, 3),
+        (20, 1),
+        (36, 1),
+        (38, 1),
+        (40, 1),
+        (64, 1),
+    ];
+
+    const EXPECTED2: [(usize, usize); 14] = [
+        (50, 32),
+        (52, 31),
+        (54, 29),
+        (56, 39),
+        (58, 25),
+        (60, 23),
+        (62, 20),
+        (64, 19),
+        (66, 12),
+        (68, 14),
+        (70, 12),
+        (72, 22),
+        (74, 4),
+        (76, 3),
+    ];
+
+    /// Cheats of the example saving at least 50 picoseconds in part 2, the
+    /// sum of [`EXPECTED2`].
+    const EXPECTED2_TOTAL: usize = 285;
+
+    #[test]
+    fn path_covers_the_track() -> Result<()> {
+        let map = parse_map(BufReader::new(TEST.as_bytes()))?;
+        let start = search_field(&map, &Cell::Start).unwrap();
+        let path = get_path(&map, &start).unwrap();
+
+        assert_eq!(Point::new(3, 1), path[0]);
+        assert_eq!(Point::new(7, 5), *path.last().unwrap());
+        // the honest race takes 84 picoseconds
+        assert_eq!(85, path.len());
+        Ok(())
+    }
+
+    #[test]
+    fn rendered_cheats_connect_track_cells() -> Result<()> {
+        let canvas = render(TEST.as_bytes(), Some(64))?;
+        assert_eq!((15, 15), (canvas.width(), canvas.height()));
+        assert_eq!(Some(Rgb::GREY), canvas.colour(Point::new(0, 0)));
+        // the single cheat saving 64 picoseconds ends on `E`
+        assert_eq!(Some(Rgb::WHITE), canvas.colour(Point::new(7, 7)));
+        assert_eq!(Some(Rgb::BLACK), canvas.colour(Point::new(7, 5)));
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn two_step_cheats_match_radius_two(
+            seed in any::<u64>(),
+            length in 1usize..81,
+            min_saving in 1usize..20,
+        ) {
+            let options = TrackOptions { width: 19, height: 19, length: Some(2 * length), seed };
+            let map = generate_track(&options).unwrap();
+            let start = search_field(&map, &Cell::Start).unwrap();
+            let path = get_path(&map, &start).unwrap();
+            prop_assert_eq!(map.find_all(|c| *c != Cell::Wall).len(), path.len());
+
+            let pairs = |cheats: Vec<Cheat>| {
+                cheats
+                    .into_iter()
+                    .map(|c| (c.from.min(c.to), c.from.max(c.to)))
+                    .sorted()
+                    .collect::<Vec<_>>()
+            };
+            let two_step = pairs(get_cheats(&path, &map, Some(min_saving)).unwrap());
+            let radius = get_cheats_rad(&path, &map, Some(2), Some(min_saving)).unwrap();
+            prop_assert_eq!(two_step, pairs(radius));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn diamond_matches_all_pairs(
+            seed in any::<u64>(),
+            radius in 0usize..12,
+            min_saving in 0usize..20,
+        ) {
+            let options = TrackOptions { width: 25, height: 19, length: None, seed };
+            let map = generate_track(&options).unwrap();
+            let path = get_path(&map, &search_field(&map, &Cell::Start).unwrap()).unwrap();
+
+            // the diamond finds the cheats of a start in another order
+            let sorted = |cheats: Vec<Cheat>| {
+                cheats.into_iter().sorted_by_key(|c| (c.from, c.to)).collect::<Vec<_>>()
+            };
+            let pairs = get_cheats_rad(&path, &map, Some(radius), Some(min_saving)).unwrap();
+            let diamond = get_cheats_diamond(&path, &map, Some(radius), Some(min_saving)).unwrap();
+            prop_assert_eq!(sorted(pairs), sorted(diamond));
+        }
+    }
+
+    #[test]
+    fn maze_cheats_leave_the_shortest_path() -> Result<()> {
+        // the dead end at the bottom is not part of the race, but cheating
+        // from its end at (7, 5) to (5, 5) saves 22 - (10 + 2 + 2) = 8
+        let map = parse_maze(
+            "\
+#########
+#S#.....#
+#.#.###.#
+#.#.#E#.#
+#.#.#.#.#
+#...#...#
+#.#######
+#.......#
+#########
+"
+            .as_bytes(),
+        )?;
+        let times = RaceTimes::new(&map)?;
+        assert_eq!(22, times.best);
+        assert_eq!(Some(8), times.saving(Point::new(7, 5), Point::new(5, 5)));
+
+        let cheats = maze_cheats(&times, Some(2), None).collect::<Vec<_>>();
+        assert!(cheats.contains(&CheatRecord {
+            start: Point::new(7, 5),
+            end: Point::new(5, 5),
+            length: 2,
+            saved: 8
+        }));
+        assert_eq!(13, cheats.len());
+
+        // the solvers following the path would miss the cheats from the
+        // dead end, so it is no racetrack
+        let err = parse_map(map.to_string().as_bytes()).unwrap_err();
+        assert_eq!(
+            "invalid racetrack map\n  \
+             <input>:7:2: 8 track cell(s) off the path from `S` to `E`: `.`",
+            err.to_string()
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn maze_cheats_match_brute_force(
+            seed in any::<u64>(),
+            length in 1usize..63,
+            openings in prop::collection::vec((1usize..14, 1usize..18), 0..30),
+            radius in 0usize..8,
+        ) {
+            let options = TrackOptions { width: 19, height: 15, length: Some(2 * length), seed };
+            let mut map = generate_track(&options).unwrap();
+            // knock down walls for branches, loops and dead ends
+            for (row, col) in openings {
+                let pos = Point::new(row, col);
+                if map[pos] == Cell::Wall {
+                    map[pos] = Cell::Track;
+                }
+            }
+
+            let map = parse_maze(map.to_string().as_bytes()).unwrap();
+            let maze = maze_histogram(&map, Some(radius), None).unwrap();
+            prop_assert_eq!(brute_force_histogram(&map, radius).unwrap(), maze);
+        }
+    }
+
+    #[test]
+    fn unknown_cell_is_reported() {
+        let err = parse_map(BufReader::new("#####\n#S.E#\n##o##\n".as_bytes())).unwrap_err();
+        let err = err.downcast::<InputError>().unwrap();
+        assert_eq!((3, 3), (err.line, err.column));
+        assert_eq!("o", err.text);
+    }
+
+    #[test]
+    fn generated_track_is_a_single_path() -> Result<()> {
+        let options = TrackOptions {
+            width: 21,
+            height: 15,
+            length: Some(60),
+            seed: 7,
+        };
+        let map = generate_track(&options)?;
+        assert_eq!((21, 15), (map.width(), map.height()));
+        assert_eq!(map, generate_track(&options)?);
+        assert_ne!(map, generate_track(&TrackOptions { seed: 8, ..options })?);
+
+        let start = search_field(&map, &Cell::Start).unwrap();
+        let path = get_path(&map, &start).unwrap();
+        assert_eq!(61, path.len());
+        assert_eq!(map.find_all(|c| *c != Cell::Wall).len(), path.len());
+
+        let longest = generate_track(&TrackOptions {
+            length: None,
+            ..options
+        })?;
+        assert_eq!(2 * 10 * 7 - 1, longest.find_all(|c| *c != Cell::Wall).len());
+
+        assert!(generate_track(&TrackOptions {
+            width: 20,
+            ..options
+        })
+        .is_err());
+        assert!(generate_track(&TrackOptions {
+            length: Some(61),
+            ..options
+        })
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn histograms_match_the_puzzle_tables() -> Result<()> {
+        type Histogram = fn(&Grid<Cell>, usize, usize) -> Result<BTreeMap<usize, usize>>;
+        let histograms: [(&str, Histogram); 4] = [
+            ("histogram", |_, radius, min| {
+                histogram(TEST.as_bytes(), Some(radius), Some(min))
+            }),
+            ("cheat_histogram", |map, radius, min| {
+                let path = get_path(map, &search_field(map, &Cell::Start)?)?;
+                cheat_histogram(&path, map, Some(radius), Some(min))
+            }),
+            ("maze_histogram", |map, radius, min| {
+                maze_histogram(map, Some(radius), Some(min))
+            }),
+            ("brute_force_histogram", |map, radius, min| {
+                Ok(brute_force_histogram(map, radius)?.split_off(&min))
+            }),
+        ];
+
+        let total = EXPECTED2.iter().map(|(_, n)| n).sum::<usize>();
+        assert_eq!(EXPECTED2_TOTAL, total);
+
+        let map = parse_map(TEST.as_bytes())?;
+        for (name, histogram) in histograms {
+            let two_step = histogram(&map, 2, 1)?;
+            assert_eq!(BTreeMap::from(EXPECTED1_SAVINGS), two_step, "{}", name);
+            let long = histogram(&map, 20, 50)?;
+            assert_eq!(BTreeMap::from(EXPECTED2), long, "{}", name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn histogram_of_a_generated_track_matches_the_cheats() -> Result<()> {
+        let map = generate_track(&TrackOptions {
+            width: 31,
+            height: 25,
+            length: Some(250),
+            seed: 2024,
+        })?;
+        let start = search_field(&map, &Cell::Start).unwrap();
+        let path = get_path(&map, &start).unwrap();
+        let two_step = get_cheats(&path, &map, Some(1))?
+            .into_iter()
+            .map(|c| c.saving(&path))
+            .counts()
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(brute_force_histogram(&map, 2)?, two_step);
+        let radius = cheat_histogram(&path, &map, Some(6), None)?;
+        assert_eq!(brute_force_histogram(&map, 6)?, radius);
+        Ok(())
+    }
+
+    #[test]
+    fn maze_cheats_are_unique() -> Result<()> {
+        let times = RaceTimes::new(&parse_maze(TEST.as_bytes())?)?;
+        let records = maze_cheats(&times, Some(2), None).collect::<Vec<_>>();
+        assert_eq!(EXPECTED1, records.len());
+        assert!(records.iter().all(|r| r.length == 2 && r.saved > 0));
+        assert!(records.iter().map(|r| (r.start, r.end)).all_unique());
+        Ok(())
+    }
+
+    #[test]
+    fn cheat_records_export() -> Result<()> {
+        let records = [CheatRecord {
+            start: Point::new(7, 7),
+            end: Point::new(7, 5),
+            length: 2,
+            saved: 64,
+        }];
+
+        let mut csv = Vec::new();
+        assert_eq!(1, write_csv(records, &mut csv)?);
+        assert_eq!(
+            "start_row,start_col,end_row,end_col,length,saved\n7,7,7,5,2,64\n",
+            String::from_utf8(csv)?
+        );
+
+        let mut json = Vec::new();
+        assert_eq!(1, write_json(records, &mut json)?);
+        let value: serde_json::Value = serde_json::from_slice(&json)?;
+        assert_eq!(5, value[0]["end"]["col"]);
+        assert_eq!(64, value[0]["saved"]);
+
+        assert!(save_records(records, Path::new("cheats.txt")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn map_problems_are_located() {
+        let map = "\
+#######
+#S..#E#
+#.#.#.#
+.S..#..
+#######
+";
+        let err = parse_map(map.as_bytes()).unwrap_err();
+        let problems = err.downcast::<InputErrors>().unwrap().problems;
+        let located = problems
+            .iter()
+            .map(|p| (p.line, p.column, p.text.as_str(), p.reason.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (4, 2, "S", "another `S`, the first one is at 2:2"),
+                (4, 1, ".", "the map must be enclosed by walls"),
+                (4, 7, ".", "the map must be enclosed by walls"),
+                (2, 6, "E", "`E` cannot be reached from `S`"),
+            ],
+            located
+        );
+
+        let err = parse_map("###\n#S#\n###\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            "invalid racetrack map\n  <input>: no `E` on the map",
+            err.to_string()
+        );
+        assert!(map_problems(&parse_map(TEST.as_bytes()).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn mazes_may_branch_but_must_reach_the_end() -> Result<()> {
+        // a sealed-off cell is allowed in a maze, but not on a racetrack
+        let maze = "#######\n#S...E#\n#######\n#.#####\n#######\n";
+        assert!(parse_map(maze.as_bytes()).is_err());
+        assert_eq!(
+            6,
+            parse_maze(maze.as_bytes())?
+                .find_all(|c| *c != Cell::Wall)
+                .len()
+        );
+
+        let sealed = "#######\n#S..#E#\n#.#.#.#\n#...###\n#######\n";
+        let err = parse_maze(sealed.as_bytes()).unwrap_err();
+        assert_eq!(
+            "invalid maze\n  <input>:2:6: `E` cannot be reached from `S`: `E`",
+            err.to_string()
+        );
+        assert!(parse_maze("#####\n#S.E.\n#####\n".as_bytes()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn map_problems_name_the_file() {
+        let input = crate::Input {
+            name: "input/20.txt".to_string(),
+            text: "#####\n#S..#\n#.#.#\n.S..#\n#####\n".to_string(),
+        };
+        let err = crate::run_variant(&Day20, 1, crate::DEFAULT_VARIANT, &input).unwrap_err();
+        assert_eq!(
+            "invalid racetrack map\n  \
+             input/20.txt: no `E` on the map\n  \
+             input/20.txt:4:2: another `S`, the first one is at 2:2: `S`\n  \
+             input/20.txt:4:1: the map must be enclosed by walls: `.`",
+            err.to_string()
+        );
+    }
+
+    crate::examples! {
+        part1_example: part1(TEST, Some(0)) => EXPECTED1;
+        part1_diamond_example: count_cheats(TEST, Some(2), Some(1), get_cheats_diamond) => EXPECTED1;
+        part2_diamond_example: count_cheats(TEST, Some(20), Some(50), get_cheats_diamond) => EXPECTED2_TOTAL;
+        part2_maze_example: count_maze_cheats(TEST, Some(20), Some(50)) => EXPECTED2_TOTAL;
+        part2_example: part2(TEST, Some(20), Some(50)) => EXPECTED2_TOTAL;
+    }
+}
@@ -0,0 +1,333 @@
+//! Rectangular grids of cells, as used by the map puzzles.
+
+use crate::point::{Direction, Point};
+use crate::InputError;
+use anyhow::*;
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from rows of equal length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if let Some(r) = rows.iter().position(|r| r.len() != width) {
+            bail!(
+                "row {} has {} cells, expected {}",
+                r + 1,
+                rows[r].len(),
+                width
+            );
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map, one row per line, mapping every character with
+    /// `cell`. Characters `cell` rejects and rows of a different length than
+    /// the first one are reported as [`InputError`].
+    pub fn parse<R: BufRead>(reader: R, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut row = Vec::new();
+            for (j, c) in line.chars().enumerate() {
+                let Some(value) = cell(c) else {
+                    let text = c.to_string();
+                    return Err(InputError::new(i + 1, j + 1, &text, "unexpected character").into());
+                };
+                row.push(value);
+            }
+
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    let reason = format!("expected {} cells, found {}", first.len(), row.len());
+                    return Err(InputError::new(i + 1, 1, &line, reason).into());
+                }
+            }
+            rows.push(row);
+        }
+
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.row * self.width + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// Position `delta` away from `pos`, if it is inside the grid.
+    pub fn offset(&self, pos: Point, delta: Point<isize>) -> Option<Point> {
+        pos.checked_offset(delta).filter(|&p| self.contains(p))
+    }
+
+    /// Neighbour of `pos` in direction `dir`, if it is inside the grid.
+    pub fn step(&self, pos: Point, dir: Direction) -> Option<Point> {
+        self.offset(pos, dir.delta())
+    }
+
+    /// The up to 4 positions sharing an edge with `pos`, clockwise from north.
+    pub fn neighbours4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The up to 8 positions sharing an edge or a corner with `pos`,
+    /// clockwise from north.
+    pub fn neighbours8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| Point::new(r, c)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell matching `pred`, row by row.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, t)| pred(t)).map(|(p, _)| p)
+    }
+
+    /// Positions of all cells matching `pred`, row by row.
+    pub fn find_all(&self, mut pred: impl FnMut(&T) -> bool) -> Vec<Point> {
+        self.iter()
+            .filter(|(_, t)| pred(t))
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|r| self.row(r))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|c| self.column(c))
+    }
+
+    /// Grid of the same shape with every cell mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Grid of the given size with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid at its main diagonal.
+    pub fn transpose(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_right(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self
+                .columns()
+                .flat_map(|c| c.collect::<Vec<_>>().into_iter().rev())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    pub fn rotate_left(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|c| self.column(c))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", pos))
+    }
+}
+
+/// Draws the grid with one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        fmt::Result::Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+ab
+cd
+ef
+";
+
+    fn p(row: usize, col: usize) -> Point {
+        Point::new(row, col)
+    }
+
+    fn grid() -> Grid<char> {
+        Grid::parse(MAP.as_bytes(), Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let g = grid();
+        assert_eq!((2, 3), (g.width(), g.height()));
+        assert_eq!('d', g[p(1, 1)]);
+        assert_eq!(None, g.get(p(3, 0)));
+        assert_eq!(MAP, g.to_string());
+    }
+
+    #[test]
+    fn parse_errors_have_positions() {
+        let err = Grid::parse("..\n.x\n".as_bytes(), |c| (c == '.').then_some(()))
+            .unwrap_err()
+            .downcast::<InputError>()
+            .unwrap();
+        assert_eq!((2, 2, "x"), (err.line, err.column, err.text.as_str()));
+
+        let err = Grid::parse("..\n...\n".as_bytes(), Some)
+            .unwrap_err()
+            .downcast::<InputError>()
+            .unwrap();
+        assert_eq!(
+            (2, "expected 2 cells, found 3"),
+            (err.line, err.reason.as_str())
+        );
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let g = grid();
+        assert_eq!(
+            vec![p(0, 1), p(1, 0)],
+            g.neighbours4(p(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![p(0, 1), p(1, 1), p(1, 0)],
+            g.neighbours8(p(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(5, g.neighbours8(p(1, 1)).count());
+        assert_eq!(None, g.offset(p(0, 1), Point::new(-1, 0)));
+        assert_eq!(Some(p(2, 1)), g.offset(p(0, 0), Point::new(2, 1)));
+    }
+
+    #[test]
+    fn find_cells() {
+        let g = grid();
+        assert_eq!(Some(p(2, 0)), g.find(|&c| c == 'e'));
+        assert_eq!(None, g.find(|&c| c == 'z'));
+        assert_eq!(
+            vec![p(0, 0), p(2, 0)],
+            g.find_all(|&c| c == 'a' || c == 'e')
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let g = grid();
+        assert_eq!(&['c', 'd'], g.row(1));
+        assert_eq!("bdf", g.column(1).collect::<String>());
+        assert_eq!(3, g.rows().count());
+        assert_eq!(2, g.columns().count());
+    }
+
+    #[test]
+    fn transpose_and_rotate() {
+        let g = grid();
+        assert_eq!("ace\nbdf\n", g.transpose().to_string());
+        assert_eq!("eca\nfdb\n", g.rotate_right().to_string());
+        assert_eq!("bdf\nace\n", g.rotate_left().to_string());
+        assert_eq!(g, g.rotate_right().rotate_left());
+        assert_eq!(g, g.transpose().transpose());
+    }
+}
@@ -0,0 +1,277 @@
+//! Loading of puzzle inputs and reporting of malformed ones.
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+
+/// Environment variable naming the directory with the puzzle inputs,
+/// `input` by default.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Path of the puzzle input of `day`, e.g. `input/01.txt`.
+pub fn input_file(day: u8) -> String {
+    let dir = env::var(INPUT_DIR_VAR).unwrap_or_else(|_| "input".to_string());
+    format!("{}/{:02}.txt", dir.trim_end_matches('/'), day)
+}
+
+/// Where the puzzle input of a day is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(String),
+}
+
+impl InputSource {
+    /// Resolves the command line argument `arg`: `-` reads stdin, anything
+    /// else is a path. Without an argument the day's default file is used,
+    /// see [`input_file`].
+    pub fn resolve(day: u8, arg: Option<&str>) -> Self {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(path.to_string()),
+            None => InputSource::File(input_file(day)),
+        }
+    }
+
+    /// Name of the source in diagnostics.
+    pub fn name(&self) -> &str {
+        match self {
+            InputSource::Stdin => "<stdin>",
+            InputSource::File(path) => path,
+        }
+    }
+
+    /// Reads the whole input and normalises it, see [`normalize`].
+    pub fn load(&self) -> io::Result<Input> {
+        let raw = match self {
+            InputSource::Stdin => {
+                let mut raw = String::new();
+                io::stdin().read_to_string(&mut raw)?;
+                raw
+            }
+            InputSource::File(path) => fs::read_to_string(path)?,
+        };
+
+        Ok(Input {
+            name: self.name().to_string(),
+            text: normalize(&raw),
+        })
+    }
+}
+
+/// A puzzle input loaded into memory, so every part can read it again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+impl Input {
+    pub fn reader(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+}
+
+/// Strips a UTF-8 byte order mark, converts CRLF line endings to LF and
+/// drops trailing blank lines, keeping a single final newline.
+pub fn normalize(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+
+    let mut lines = raw
+        .split('\n')
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .collect::<Vec<_>>();
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let mut text = lines.join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+/// A piece of puzzle input that could not be parsed.
+///
+/// Parsers only see a reader, so they leave `file` empty; the runner fills it
+/// in with [`InputError::attach_file`] once the error reaches it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputError {
+    pub file: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    pub reason: String,
+}
+
+impl InputError {
+    pub fn new(line: usize, column: usize, text: &str, reason: impl Into<String>) -> Self {
+        InputError {
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Error about `token`, which must be a subslice of `line_text`, the
+    /// text of line `line`; the column is derived from its position.
+    pub fn at(line: usize, line_text: &str, token: &str, reason: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line_text.as_ptr() as usize)
+            .filter(|&o| o <= line_text.len())
+            .expect("token is not part of the line");
+        let column = line_text[..offset].chars().count() + 1;
+        InputError::new(line, column, token, reason)
+    }
+
+    /// Sets the input file of `err` if it is an `InputError`, or of every
+    /// problem if it is an [`InputErrors`].
+    pub fn attach_file(err: anyhow::Error, file: &str) -> anyhow::Error {
+        let err = match err.downcast::<InputError>() {
+            Ok(mut e) => {
+                e.file = Some(file.to_string());
+                return e.into();
+            }
+            Err(err) => err,
+        };
+        match err.downcast::<InputErrors>() {
+            Ok(mut e) => {
+                e.file = Some(file.to_string());
+                for problem in &mut e.problems {
+                    problem.file = Some(file.to_string());
+                }
+                e.into()
+            }
+            Err(err) => err,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: `{}`",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            self.reason,
+            self.text
+        )
+    }
+}
+
+impl error::Error for InputError {}
+
+/// Everything wrong with a puzzle input that is checked as a whole, like a
+/// map, instead of stopping at the first problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputErrors {
+    pub file: Option<String>,
+    /// What is wrong overall, e.g. `invalid racetrack map`.
+    pub summary: String,
+    /// Problems of the whole input, which have no position.
+    pub general: Vec<String>,
+    /// Problems at a position in the input.
+    pub problems: Vec<InputError>,
+}
+
+impl InputErrors {
+    pub fn new(summary: impl Into<String>) -> Self {
+        InputErrors {
+            file: None,
+            summary: summary.into(),
+            general: Vec::new(),
+            problems: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.general.is_empty() && self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for InputErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.summary)?;
+        for reason in &self.general {
+            write!(
+                f,
+                "\n  {}: {}",
+                self.file.as_deref().unwrap_or("<input>"),
+                reason
+            )?;
+        }
+        for problem in &self.problems {
+            write!(f, "\n  {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for InputErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_is_normalized() {
+        assert_eq!(
+            "3  4\n4  3\n",
+            normalize("\u{feff}3  4\r\n4  3\r\n\r\n  \n")
+        );
+        assert_eq!("125 17\n", normalize("125 17"));
+        assert_eq!(" a \n\nb\n", normalize(" a \n\nb\n"));
+        assert_eq!("", normalize("\n\n"));
+    }
+
+    #[test]
+    fn sources_are_resolved() {
+        assert_eq!(InputSource::Stdin, InputSource::resolve(1, Some("-")));
+        assert_eq!(
+            InputSource::File("other/01.txt".to_string()),
+            InputSource::resolve(1, Some("other/01.txt"))
+        );
+        assert_eq!(
+            InputSource::File(input_file(1)),
+            InputSource::resolve(1, None)
+        );
+    }
+
+    #[test]
+    fn column_of_token() {
+        let line = "12 ab 7";
+        let e = InputError::at(3, line, &line[3..5], "expected a number");
+        assert_eq!((3, 4), (e.line, e.column));
+        assert_eq!("<input>:3:4: expected a number: `ab`", e.to_string());
+    }
+
+    #[test]
+    fn file_is_attached() {
+        let e = InputError::attach_file(InputError::new(1, 2, "x", "bad").into(), "input/01.txt");
+        assert_eq!("input/01.txt:1:2: bad: `x`", e.to_string());
+
+        let mut errors = InputErrors::new("invalid map");
+        errors.general.push("no `E`".to_string());
+        errors
+            .problems
+            .push(InputError::new(2, 3, "S", "another `S`"));
+        let e = InputError::attach_file(errors.into(), "input/20.txt");
+        assert_eq!(
+            "invalid map\n  input/20.txt: no `E`\n  input/20.txt:2:3: another `S`: `S`",
+            e.to_string()
+        );
+
+        let other = InputError::attach_file(anyhow::anyhow!("other"), "input/01.txt");
+        assert_eq!("other", other.to_string());
+    }
+}
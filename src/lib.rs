@@ -1,15 +1,224 @@
+use anyhow::*;
+use code_timing_macros::time_snippet;
+use std::io::BufRead;
+
+pub mod answers;
+pub mod bench;
+pub mod compare;
+pub mod day01;
+pub mod day02;
+pub mod day11;
+pub mod day20;
+pub mod grid;
+pub mod input;
+pub mod log;
+pub mod memo;
+pub mod parallel;
+pub mod parse;
+pub mod point;
+pub mod render;
+pub mod scaffold;
+pub mod search;
+pub mod summary;
+
+pub use grid::Grid;
+pub use input::{input_file, Input, InputError, InputErrors, InputSource, INPUT_DIR_VAR};
+pub use point::{Direction, Point};
+
+/// Every implemented day, in calendar order.
+pub const SOLUTIONS: &[&dyn Solution] =
+    &[&day01::Day01, &day02::Day02, &day11::Day11, &day20::Day20];
+
+/// The solution of one day of the calendar.
+///
+/// Both parts read the puzzle input from `reader`, so the same implementation
+/// runs on the example in the tests and on `input/NN.txt`.
+pub trait Solution {
+    /// Day of the puzzle, `1..=25`.
+    fn day(&self) -> u8;
+
+    fn part1(&self, reader: &mut dyn BufRead) -> Result<usize>;
+
+    fn part2(&self, reader: &mut dyn BufRead) -> Result<usize>;
+
+    /// Picture of the puzzle input for debugging, if the day can draw one.
+    fn render(&self, _reader: &mut dyn BufRead) -> Result<Option<render::Canvas>> {
+        Ok(None)
+    }
+
+    /// Other ways to solve the parts, next to the [`DEFAULT_VARIANT`] of
+    /// `part1` and `part2`.
+    fn variants(&self) -> &'static [Variant] {
+        &[]
+    }
+}
+
+/// Name of the variant implemented by [`Solution::part1`] and
+/// [`Solution::part2`].
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// A named alternative implementation of one part of a day.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&mut dyn BufRead) -> Result<usize>,
+}
+
+/// Looks up the solution registered for `day`.
+pub fn solution(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
 pub fn start_day(day: &str) {
     println!("Advent of Code 2024 - Day {:0>2}", day);
 }
 
+/// Solves one part of `solution` for the puzzle input in `reader`.
+pub fn solve(solution: &dyn Solution, part: u8, reader: &mut dyn BufRead) -> Result<usize> {
+    match part {
+        1 => solution.part1(reader),
+        2 => solution.part2(reader),
+        _ => bail!("part must be 1 or 2, got {}", part),
+    }
+}
+
+/// Names of the variants of one part of `solution`, the default one first.
+pub fn variant_names(solution: &dyn Solution, part: u8) -> Vec<&'static str> {
+    let others = solution.variants().iter().filter(|v| v.part == part);
+    [DEFAULT_VARIANT]
+        .into_iter()
+        .chain(others.map(|v| v.name))
+        .collect()
+}
+
+/// Solves one part of `solution` with the variant called `name`.
+pub fn solve_variant(
+    solution: &dyn Solution,
+    part: u8,
+    name: &str,
+    reader: &mut dyn BufRead,
+) -> Result<usize> {
+    if name == DEFAULT_VARIANT {
+        return solve(solution, part, reader);
+    }
+    let variant = solution
+        .variants()
+        .iter()
+        .find(|v| v.part == part && v.name == name)
+        .with_context(|| {
+            format!(
+                "day {} part {} has no variant `{}`, expected one of: {}",
+                solution.day(),
+                part,
+                name,
+                variant_names(solution, part).join(", ")
+            )
+        })?;
+    (variant.solve)(reader)
+}
+
+/// Runs one part of `solution` on `input` and prints the answer.
+pub fn run_part(solution: &dyn Solution, part: u8, input: &Input) -> Result<usize> {
+    run_variant(solution, part, DEFAULT_VARIANT, input)
+}
+
+/// Runs the variant `name` of one part of `solution` on `input` and prints
+/// the answer.
+pub fn run_variant(solution: &dyn Solution, part: u8, name: &str, input: &Input) -> Result<usize> {
+    let result = time_snippet!(solve_variant(solution, part, name, &mut input.reader())
+        .map_err(|e| InputError::attach_file(e, &input.name))?);
+    println!("Result = {}", result);
+
+    Ok(result)
+}
+
+/// Loads the puzzle input from `source`.
+pub fn load_input(source: &InputSource) -> Result<Input> {
+    source
+        .load()
+        .with_context(|| format!("cannot read {}", source.name()))
+}
+
+/// Runs both parts of `solution` on the puzzle input from `source` and
+/// returns their answers.
+pub fn run_day(solution: &dyn Solution, source: &InputSource) -> Result<[usize; 2]> {
+    let input = load_input(source)?;
+    start_day(&solution.day().to_string());
+
+    println!("=== Part 1 ===");
+    let answer1 = run_part(solution, 1, &input)?;
+
+    println!("\n=== Part 2 ===");
+    let answer2 = run_part(solution, 2, &input)?;
+
+    Ok([answer1, answer2])
+}
+
+/// Generates a `#[test]` for every example of a day, checking the answer a
+/// part function returns for the example input:
+///
+/// ```ignore
+/// examples! {
+///     part1_example: part1(TEST) => 44;
+///     #[ignore = "part 2 is not solved yet"]
+///     part2_example: part2(TEST, Some(20), Some(50)) => 285;
+/// }
+/// ```
+///
+/// The example text is passed as the reader, further arguments are passed on
+/// as they are.
+#[macro_export]
+macro_rules! examples {
+    ($($(#[$meta:meta])* $name:ident: $part:ident($input:expr $(, $arg:expr)*) => $expected:expr;)*) => {
+        $(
+            #[test]
+            $(#[$meta])*
+            fn $name() -> ::anyhow::Result<()> {
+                assert_eq!($expected, $part($input.as_bytes() $(, $arg)*)?);
+                ::std::result::Result::Ok(())
+            }
+        )*
+    };
+}
+
 // Additional common functions
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
 
     #[test]
     fn it_works() {
         start_day("00");
     }
+
+    #[test]
+    fn solutions_are_unique_and_sorted() {
+        let days = SOLUTIONS.iter().map(|s| s.day()).collect::<Vec<_>>();
+        assert!(days.windows(2).all(|w| w[0] < w[1]), "{:?}", days);
+        assert_eq!(Some(20), solution(20).map(|s| s.day()));
+        assert!(solution(25).is_none());
+    }
+
+    #[test]
+    fn variants_are_unique_per_part() {
+        for solution in SOLUTIONS {
+            for part in 1..=2 {
+                let names = variant_names(*solution, part);
+                assert_eq!(names.len(), names.iter().unique().count(), "{:?}", names);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_variant_is_reported() {
+        let err = solve_variant(&day11::Day11, 1, "magic", &mut "0".as_bytes()).unwrap_err();
+        assert!(
+            err.to_string().contains("expected one of: default"),
+            "{}",
+            err
+        );
+    }
 }
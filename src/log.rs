@@ -0,0 +1,147 @@
+//! Leveled debug output of the solvers.
+//!
+//! Messages go to stderr, so they never mix with the answers on stdout, and
+//! everything below [`Level::Warn`] is off unless enabled with `--verbose` or
+//! the `AOC_LOG` environment variable.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Environment variable setting the level, e.g. `AOC_LOG=debug`.
+pub const LOG_VAR: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    /// The level enabled by passing `-v` `count` times.
+    pub fn from_verbosity(count: usize) -> Self {
+        match count {
+            0 => Level::Warn,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Level::ALL
+            .into_iter()
+            .find(|l| l.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown log level `{}`", s))
+    }
+}
+
+/// `0` until the level is set or read from the environment.
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Enables all messages up to `level`, overriding `AOC_LOG`.
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// The current level, taken from `AOC_LOG` on first use.
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => {
+            let level = std::env::var(LOG_VAR)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Level::Warn);
+            set_level(level);
+            level
+        }
+        n => Level::ALL[n as usize - 1],
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, args: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("[{}] {}", level, args);
+    }
+}
+
+/// Logs a message at the given [`Level`]; the arguments are only formatted
+/// if the level is enabled.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_parse_and_order() {
+        assert_eq!(Result::Ok(Level::Debug), "DEBUG".parse());
+        assert!("loud".parse::<Level>().is_err());
+        assert!(Level::Trace > Level::Debug && Level::Warn > Level::Error);
+        assert_eq!(Level::Warn, Level::from_verbosity(0));
+        assert_eq!(Level::Trace, Level::from_verbosity(3));
+    }
+
+    #[test]
+    fn set_level_enables_lower_levels() {
+        set_level(Level::Debug);
+        assert_eq!(Level::Debug, level());
+        assert!(enabled(Level::Info) && enabled(Level::Debug));
+        assert!(!enabled(Level::Trace));
+        set_level(Level::Warn);
+    }
+}
@@ -0,0 +1,197 @@
+//! Memoization of recursive functions.
+//!
+//! A [`Memo`] is passed down the recursion instead of a bare map. It keeps
+//! track of how often the cache helped and can bound its own size.
+
+use anyhow::*;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+
+/// What to do when a bounded cache is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Eviction {
+    /// Never drop anything.
+    #[default]
+    Unbounded,
+    /// Keep at most this many entries, dropping the oldest one first.
+    Fifo(usize),
+    /// Keep at most this many entries, dropping all of them once full.
+    Clear(usize),
+}
+
+/// Counters of a [`Memo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+    /// Entries currently cached.
+    pub size: usize,
+}
+
+impl MemoStats {
+    /// Share of the lookups answered by the cache, `0.0` without lookups.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} evictions, {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.evictions,
+            self.size
+        )
+    }
+}
+
+/// Cache of the results of a function of `K`.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    /// Insertion order, only kept for [`Eviction::Fifo`].
+    order: VecDeque<K>,
+    eviction: Eviction,
+    stats: MemoStats,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::with_eviction(Eviction::Unbounded)
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo::default()
+    }
+
+    pub fn with_eviction(eviction: Eviction) -> Self {
+        Memo {
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            eviction,
+            stats: MemoStats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            size: self.cache.len(),
+            ..self.stats
+        }
+    }
+
+    /// Returns the cached value of `key`, or computes it with `f` and caches
+    /// it. `f` gets the memo back to recurse with.
+    pub fn call(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(v) = self.lookup(&key) {
+            return v;
+        }
+        let v = f(self);
+        self.insert(key, v.clone());
+        v
+    }
+
+    /// Like [`Memo::call`] for fallible functions, errors are not cached.
+    pub fn try_call(&mut self, key: K, f: impl FnOnce(&mut Self) -> Result<V>) -> Result<V> {
+        if let Some(v) = self.lookup(&key) {
+            return Ok(v);
+        }
+        let v = f(self)?;
+        self.insert(key, v.clone());
+        Ok(v)
+    }
+
+    fn lookup(&mut self, key: &K) -> Option<V> {
+        let v = self.cache.get(key).cloned();
+        if v.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        v
+    }
+
+    /// Caches `value` for `key` only once the value is complete, so results
+    /// of the recursion below `key` never see a partial entry.
+    fn insert(&mut self, key: K, value: V) {
+        match self.eviction {
+            Eviction::Unbounded => {}
+            Eviction::Fifo(capacity) => {
+                while self.cache.len() >= capacity.max(1) {
+                    let Some(oldest) = self.order.pop_front() else {
+                        break;
+                    };
+                    self.cache.remove(&oldest);
+                    self.stats.evictions += 1;
+                }
+                self.order.push_back(key.clone());
+            }
+            Eviction::Clear(capacity) => {
+                if self.cache.len() >= capacity.max(1) {
+                    self.stats.evictions += self.cache.len();
+                    self.cache.clear();
+                }
+            }
+        }
+        self.cache.insert(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.call(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+    }
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let mut memo = Memo::new();
+        assert_eq!(12586269025, fib(50, &mut memo));
+        let stats = memo.stats();
+        assert_eq!(
+            (47, 49, 49, 0),
+            (stats.hits, stats.misses, stats.size, stats.evictions)
+        );
+
+        assert_eq!(55, fib(10, &mut memo));
+        assert_eq!(48, memo.stats().hits);
+    }
+
+    #[test]
+    fn bounded_caches_evict() {
+        for eviction in [Eviction::Fifo(8), Eviction::Clear(8)] {
+            let mut memo = Memo::with_eviction(eviction);
+            assert_eq!(12586269025, fib(50, &mut memo));
+            let stats = memo.stats();
+            assert!(stats.size <= 8, "{:?}: {}", eviction, stats);
+            assert!(stats.evictions > 0, "{:?}: {}", eviction, stats);
+        }
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let mut memo = Memo::<u8, u8>::new();
+        assert!(memo.try_call(1, |_| bail!("no value")).is_err());
+        assert_eq!(7, memo.try_call(1, |_| Ok(7)).unwrap());
+        assert_eq!(7, memo.try_call(1, |_| bail!("not called")).unwrap());
+        assert_eq!((1, 2), (memo.stats().hits, memo.stats().misses));
+    }
+}
@@ -0,0 +1,37 @@
+//! Opt-in parallelism of the heavy solvers.
+//!
+//! With the `parallel` feature the outer loop of the day 20 cheat search and
+//! the stones of day 11 are spread over a [rayon](https://docs.rs/rayon)
+//! thread pool, one thread per core unless set with [`set_threads`]. The
+//! answers are the same as without the feature.
+
+use anyhow::*;
+
+/// Runs the parallel solvers on `threads` threads. Must be called before
+/// the first solver runs; only a single thread is possible without the
+/// `parallel` feature.
+pub fn set_threads(threads: usize) -> Result<()> {
+    ensure!(threads > 0, "at least one thread is needed");
+    #[cfg(feature = "parallel")]
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .context("cannot start the thread pool")?;
+    #[cfg(not(feature = "parallel"))]
+    ensure!(
+        threads == 1,
+        "{} threads need a build with `--features parallel`",
+        threads
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_threads_are_rejected() {
+        assert!(set_threads(0).is_err());
+    }
+}
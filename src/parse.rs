@@ -0,0 +1,222 @@
+//! Helpers for the usual shapes of puzzle input. Everything that cannot be
+//! parsed is reported as an [`InputError`] pointing at the offending text.
+
+use crate::InputError;
+use anyhow::*;
+use regex::{Captures, Regex};
+use std::fmt::Display;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+static INT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[-+]?\d+").unwrap());
+
+/// Parses `token`, a subslice of `line_text`, the text of line `line`.
+pub fn token<T>(line: usize, line_text: &str, token: &str) -> Result<T, InputError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|e| InputError::at(line, line_text, token, format!("invalid number, {}", e)))
+}
+
+/// Every integer in `text`, the text of line `line`, ignoring whatever
+/// separates them. A sign directly in front of the digits belongs to the
+/// number.
+pub fn ints<T>(line: usize, text: &str) -> Result<Vec<T>, InputError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    INT.find_iter(text)
+        .map(|m| token(line, text, m.as_str()))
+        .collect()
+}
+
+/// The whitespace separated fields of `text`, the text of line `line`.
+pub fn fields<T>(line: usize, text: &str) -> Result<Vec<T>, InputError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.split_whitespace()
+        .map(|f| token(line, text, f))
+        .collect()
+}
+
+/// Parses a table of `N` whitespace separated columns into one vector per
+/// column.
+pub fn columns<T, const N: usize, R: BufRead>(reader: R) -> Result<[Vec<T>; N]>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let mut columns = std::array::from_fn(|_| Vec::new());
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let row = fields::<T>(i + 1, &line)?;
+        if row.len() != N {
+            let reason = format!("expected {} columns, found {}", N, row.len());
+            return Err(InputError::new(i + 1, 1, &line, reason).into());
+        }
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// A block of input lines without blank lines in between.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// 1-based line number of the first line.
+    pub line: usize,
+    pub lines: Vec<String>,
+}
+
+impl Section {
+    /// The lines with their 1-based line numbers.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &str)> {
+        (self.line..).zip(self.lines.iter().map(String::as_str))
+    }
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+pub fn sections<R: BufRead>(reader: R) -> Result<Vec<Section>> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut in_section = false;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            in_section = false;
+        } else if in_section {
+            sections.last_mut().unwrap().lines.push(line);
+        } else {
+            sections.push(Section {
+                line: i + 1,
+                lines: vec![line],
+            });
+            in_section = true;
+        }
+    }
+
+    Ok(sections)
+}
+
+/// A tuple that can be built from the capture groups of a pattern, one
+/// group per element.
+pub trait FromCaptures: Sized {
+    fn from_captures(line: usize, text: &str, caps: &Captures) -> Result<Self, InputError>;
+}
+
+macro_rules! impl_from_captures {
+    ($($t:ident $n:tt),+) => {
+        impl<$($t),+> FromCaptures for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Display,)+
+        {
+            fn from_captures(line: usize, text: &str, caps: &Captures) -> Result<Self, InputError> {
+                std::result::Result::Ok(($(
+                    {
+                        let group = caps.get($n).ok_or_else(|| {
+                            InputError::new(line, 1, text, format!("group {} did not match", $n))
+                        })?;
+                        group.as_str().parse::<$t>().map_err(|e| {
+                            InputError::at(line, text, group.as_str(), format!("invalid value, {}", e))
+                        })?
+                    },
+                )+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(A 1);
+impl_from_captures!(A 1, B 2);
+impl_from_captures!(A 1, B 2, C 3);
+impl_from_captures!(A 1, B 2, C 3, D 4);
+impl_from_captures!(A 1, B 2, C 3, D 4, E 5);
+impl_from_captures!(A 1, B 2, C 3, D 4, E 5, F 6);
+
+/// Parses every line with the regular expression `pattern` into a tuple of
+/// its capture groups, e.g. `(usize, usize)` for `r"^(\d+),(\d+)$"`.
+pub fn tuples<T: FromCaptures, R: BufRead>(reader: R, pattern: &str) -> Result<Vec<T>> {
+    let re = Regex::new(pattern)?;
+    let mut tuples = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let caps = re.captures(&line).ok_or_else(|| {
+            InputError::new(
+                i + 1,
+                1,
+                &line,
+                format!("expected a match of `{}`", pattern),
+            )
+        })?;
+        tuples.push(T::from_captures(i + 1, &line, &caps)?);
+    }
+
+    Ok(tuples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_error(err: Error) -> InputError {
+        err.downcast::<InputError>().unwrap()
+    }
+
+    #[test]
+    fn signed_ints() -> Result<()> {
+        assert_eq!(vec![3, -4, 12, 5], ints::<isize>(1, "p=3,-4 v=+12 x5")?);
+        assert!(ints::<isize>(1, "no numbers").unwrap().is_empty());
+
+        let err = ints::<u8>(4, "1 -2").unwrap_err();
+        assert_eq!((4, 3, "-2"), (err.line, err.column, err.text.as_str()));
+        Ok(())
+    }
+
+    #[test]
+    fn column_table() -> Result<()> {
+        let [a, b] = columns::<usize, 2, _>("3  4\n4  3\n".as_bytes())?;
+        assert_eq!((vec![3, 4], vec![4, 3]), (a, b));
+
+        let err = input_error(columns::<usize, 2, _>("3  4\n4\n".as_bytes()).unwrap_err());
+        assert_eq!(
+            (2, "expected 2 columns, found 1"),
+            (err.line, err.reason.as_str())
+        );
+
+        let err = input_error(columns::<usize, 2, _>("3  4\n4 x3\n".as_bytes()).unwrap_err());
+        assert_eq!((2, 3, "x3"), (err.line, err.column, err.text.as_str()));
+        Ok(())
+    }
+
+    #[test]
+    fn blank_line_sections() -> Result<()> {
+        let s = sections("a\nb\n\n\nc\n\n".as_bytes())?;
+        assert_eq!(2, s.len());
+        assert_eq!(vec!["a", "b"], s[0].lines);
+        assert_eq!(vec![(5, "c")], s[1].numbered().collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn lines_to_tuples() -> Result<()> {
+        let pattern = r"^(\w+) -> (\d+)$";
+        let t = tuples::<(String, u32), _>("a -> 1\nbc -> 23\n".as_bytes(), pattern)?;
+        assert_eq!(vec![("a".to_string(), 1), ("bc".to_string(), 23)], t);
+
+        let err =
+            input_error(tuples::<(String, u8), _>("a -> 300\n".as_bytes(), pattern).unwrap_err());
+        assert_eq!((1, 6, "300"), (err.line, err.column, err.text.as_str()));
+
+        let err = input_error(tuples::<(String, u8), _>("a 1\n".as_bytes(), pattern).unwrap_err());
+        assert_eq!((1, 1, "a 1"), (err.line, err.column, err.text.as_str()));
+        Ok(())
+    }
+}
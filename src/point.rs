@@ -0,0 +1,217 @@
+//! Points on a grid and the directions between them.
+
+use serde::Serialize;
+use std::fmt;
+
+/// A point as `(row, column)`, unsigned by default. Signed points are used for
+/// offsets and for positions that may leave the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct Point<T = usize> {
+    pub row: T,
+    pub col: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(row: T, col: T) -> Self {
+        Point { row, col }
+    }
+}
+
+macro_rules! impl_point {
+    ($t:ty, $add:ident) => {
+        impl Point<$t> {
+            /// Point `delta` away, or `None` if a coordinate over- or underflows.
+            pub fn checked_offset(self, delta: Point<isize>) -> Option<Self> {
+                Some(Point {
+                    row: self.row.$add(delta.row)?,
+                    col: self.col.$add(delta.col)?,
+                })
+            }
+
+            /// The neighbouring point in direction `dir`.
+            pub fn step(self, dir: Direction) -> Option<Self> {
+                self.checked_offset(dir.delta())
+            }
+
+            /// Number of orthogonal steps between the two points.
+            pub fn manhattan(self, other: Self) -> usize {
+                self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+            }
+
+            /// Number of king moves between the two points.
+            pub fn chebyshev(self, other: Self) -> usize {
+                self.row
+                    .abs_diff(other.row)
+                    .max(self.col.abs_diff(other.col))
+            }
+
+            /// Every point at a Manhattan distance of at most `radius`,
+            /// including `self`, row by row. Points that do not fit the
+            /// coordinate type are left out.
+            pub fn within_manhattan(self, radius: usize) -> impl Iterator<Item = Self> {
+                let r = radius as isize;
+                (-r..=r).flat_map(move |dr| {
+                    let w = r - dr.abs();
+                    (-w..=w).filter_map(move |dc| self.checked_offset(Point::new(dr, dc)))
+                })
+            }
+        }
+    };
+}
+
+impl_point!(usize, checked_add_signed);
+impl_point!(isize, checked_add);
+
+impl TryFrom<Point> for Point<isize> {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: Point) -> Result<Self, Self::Error> {
+        Result::Ok(Point::new(p.row.try_into()?, p.col.try_into()?))
+    }
+}
+
+impl TryFrom<Point<isize>> for Point {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: Point<isize>) -> Result<Self, Self::Error> {
+        Result::Ok(Point::new(p.row.try_into()?, p.col.try_into()?))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// A compass direction, north pointing to the first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// The four directions sharing an edge, clockwise from north.
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Offset of one step in this direction.
+    pub fn delta(self) -> Point<isize> {
+        let (row, col) = match self {
+            Direction::N => (-1, 0),
+            Direction::NE => (-1, 1),
+            Direction::E => (0, 1),
+            Direction::SE => (1, 1),
+            Direction::S => (1, 0),
+            Direction::SW => (1, -1),
+            Direction::W => (0, -1),
+            Direction::NW => (-1, -1),
+        };
+        Point::new(row, col)
+    }
+
+    /// The direction 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self.index() + 2) % 8]
+    }
+
+    /// The direction 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self.index() + 6) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::ALL[(self.index() + 4) % 8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point::<usize>::new(3, 1);
+        let b = Point::new(7, 5);
+        assert_eq!(8, a.manhattan(b));
+        assert_eq!(8, b.manhattan(a));
+        assert_eq!(4, a.chebyshev(b));
+        // the deltas have opposite signs
+        let c = Point::<usize>::new(1, 6);
+        assert_eq!(7, a.manhattan(c));
+        assert_eq!(7, c.manhattan(a));
+        assert_eq!(7, Point::<isize>::new(-2, 3).manhattan(Point::new(1, -1)));
+    }
+
+    #[test]
+    fn checked_steps() {
+        let p = Point::<usize>::new(0, 2);
+        assert_eq!(None, p.step(Direction::N));
+        assert_eq!(Some(Point::new(1, 1)), p.step(Direction::SW));
+        assert_eq!(
+            Some(Point::new(-1, 2)),
+            Point::<isize>::new(0, 2).step(Direction::N)
+        );
+        assert_eq!(None, Point::new(isize::MAX, 0).step(Direction::S));
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::E, Direction::N.turn_right());
+        assert_eq!(Direction::W, Direction::N.turn_left());
+        assert_eq!(Direction::NW, Direction::SW.turn_right());
+        assert_eq!(Direction::SE, Direction::NW.opposite());
+        for d in Direction::ALL {
+            assert_eq!(d, d.turn_right().turn_left());
+            assert_eq!(d.opposite(), d.turn_right().turn_right());
+            let back = d.delta().checked_offset(d.opposite().delta());
+            assert_eq!(Some(Point::new(0, 0)), back);
+        }
+    }
+
+    #[test]
+    fn manhattan_radius() {
+        let centre = Point::<isize>::new(0, 0);
+        for r in 0..5 {
+            let points = centre.within_manhattan(r).collect::<Vec<_>>();
+            assert_eq!(2 * r * (r + 1) + 1, points.len());
+            assert!(points.iter().all(|p| p.manhattan(centre) <= r));
+        }
+
+        // points with negative coordinates are left out
+        let corner = Point::<usize>::new(0, 1)
+            .within_manhattan(1)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(0, 2),
+                Point::new(1, 1)
+            ],
+            corner
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Pictures of grids for debugging, on the terminal or as image files.
+//!
+//! A [`Canvas`] starts with one coloured glyph per grid cell; overlays for
+//! paths, pairs of cells and distance heat maps are painted on top in the
+//! order they are added.
+
+use crate::grid::Grid;
+use crate::point::Point;
+use anyhow::*;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(96, 96, 96);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const GREEN: Rgb = Rgb(40, 180, 60);
+    pub const BLUE: Rgb = Rgb(40, 80, 220);
+    pub const YELLOW: Rgb = Rgb(240, 200, 40);
+
+    /// Colour of `t` in `0.0..=1.0` on a blue, cyan, green, yellow, red scale.
+    pub fn heat(t: f64) -> Rgb {
+        const STOPS: [(f64, f64, f64); 5] = [
+            (0.0, 0.0, 255.0),
+            (0.0, 255.0, 255.0),
+            (0.0, 255.0, 0.0),
+            (255.0, 255.0, 0.0),
+            (255.0, 0.0, 0.0),
+        ];
+        let x = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+        let i = (x as usize).min(STOPS.len() - 2);
+        let f = x - i as f64;
+        let (a, b) = (STOPS[i], STOPS[i + 1]);
+        let lerp = |a: f64, b: f64| (a + (b - a) * f).round() as u8;
+        Rgb(lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+    }
+
+    /// Black or white, whichever is easier to read on `self`.
+    fn contrast(self) -> Rgb {
+        let luma = 299 * self.0 as u32 + 587 * self.1 as u32 + 114 * self.2 as u32;
+        if luma > 128_000 {
+            Rgb::BLACK
+        } else {
+            Rgb::WHITE
+        }
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// A grid of coloured glyphs with connections between cells.
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    glyphs: Grid<(char, Rgb)>,
+    /// Lines drawn between two cells, e.g. from a cheat start to its end.
+    links: Vec<(Point, Point, Rgb)>,
+}
+
+impl Canvas {
+    /// Draws every cell of `grid` with the first character of its `Display`
+    /// output on the background given by `colour`.
+    pub fn from_grid<T: fmt::Display>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) -> Self {
+        Canvas {
+            glyphs: grid.map(|t| (t.to_string().chars().next().unwrap_or(' '), colour(t))),
+            links: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.glyphs.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.glyphs.height()
+    }
+
+    /// Background colour of `pos`.
+    pub fn colour(&self, pos: Point) -> Option<Rgb> {
+        self.glyphs.get(pos).map(|&(_, c)| c)
+    }
+
+    fn paint(&mut self, pos: Point, colour: Rgb) {
+        if let Some((_, c)) = self.glyphs.get_mut(pos) {
+            *c = colour;
+        }
+    }
+
+    /// Paints the cells of `path`.
+    pub fn path(mut self, path: impl IntoIterator<Item = Point>, colour: Rgb) -> Self {
+        for pos in path {
+            self.paint(pos, colour);
+        }
+        self
+    }
+
+    /// Paints the first cell of every pair with `start`, the second one with
+    /// `end` and connects them with a line of the `start` colour.
+    pub fn pairs(
+        mut self,
+        pairs: impl IntoIterator<Item = (Point, Point)>,
+        start: Rgb,
+        end: Rgb,
+    ) -> Self {
+        for (a, b) in pairs {
+            self.paint(a, start);
+            self.paint(b, end);
+            self.links.push((a, b, start));
+        }
+        self
+    }
+
+    /// Paints every cell with a distance on a scale from blue for the
+    /// smallest to red for the largest one.
+    pub fn heat_map(mut self, dist: &Grid<Option<usize>>) -> Self {
+        let values = dist.iter().filter_map(|(_, d)| *d);
+        let (lo, hi) = values.fold((usize::MAX, 0), |(lo, hi), d| (lo.min(d), hi.max(d)));
+        let span = hi.saturating_sub(lo).max(1) as f64;
+        for (pos, d) in dist.iter() {
+            if let Some(d) = d {
+                self.paint(pos, Rgb::heat((d - lo) as f64 / span));
+            }
+        }
+        self
+    }
+
+    /// The picture as text with 24-bit ANSI colours, one line per row.
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.glyphs.rows() {
+            for &(glyph, bg) in row {
+                let fg = bg.contrast();
+                write!(
+                    out,
+                    "\x1b[38;2;{};{};{};48;2;{};{};{}m{}",
+                    fg.0, fg.1, fg.2, bg.0, bg.1, bg.2, glyph
+                )
+                .unwrap();
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+
+    /// Binary PPM image with `scale` × `scale` pixels per cell. Links are
+    /// not drawn, only their end cells are coloured.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let (w, h) = (self.width() * scale, self.height() * scale);
+        let mut out = format!("P6\n{} {}\n255\n", w, h).into_bytes();
+        for row in self.glyphs.rows() {
+            let line = row
+                .iter()
+                .flat_map(|&(_, Rgb(r, g, b))| [r, g, b].repeat(scale))
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                out.extend_from_slice(&line);
+            }
+        }
+        out
+    }
+
+    /// SVG image with squares of `scale` pixels per cell and the links drawn
+    /// as lines between the cell centres.
+    pub fn to_svg(&self, scale: usize) -> String {
+        let s = scale.max(1);
+        let mut out = String::new();
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            self.width() * s,
+            self.height() * s
+        )
+        .unwrap();
+        for (pos, (_, colour)) in self.glyphs.iter() {
+            writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                pos.col * s,
+                pos.row * s,
+                colour
+            )
+            .unwrap();
+        }
+        let centre = |p: usize| p * s + s / 2;
+        for &(a, b, colour) in &self.links {
+            writeln!(
+                out,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+                centre(a.col),
+                centre(a.row),
+                centre(b.col),
+                centre(b.row),
+                colour,
+                (s / 4).max(1)
+            )
+            .unwrap();
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Writes the picture to `path` as PPM or SVG, chosen by its extension.
+    pub fn save(&self, path: &Path, scale: usize) -> Result<()> {
+        let data = match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => self.to_ppm(scale),
+            Some("svg") => self.to_svg(scale).into_bytes(),
+            _ => bail!("{}: expected a .ppm or .svg file", path.display()),
+        };
+        fs::write(path, data).with_context(|| format!("cannot write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(row: usize, col: usize) -> Point {
+        Point::new(row, col)
+    }
+
+    fn canvas() -> Canvas {
+        let grid = Grid::parse("#.#\n...\n".as_bytes(), Some).unwrap();
+        Canvas::from_grid(&grid, |&c| if c == '#' { Rgb::GREY } else { Rgb::BLACK })
+    }
+
+    #[test]
+    fn overlays_paint_in_order() {
+        let c = canvas().path([p(1, 0), p(1, 1)], Rgb::GREEN).pairs(
+            [(p(1, 1), p(0, 1))],
+            Rgb::RED,
+            Rgb::BLUE,
+        );
+        assert_eq!(Some(Rgb::GREY), c.colour(p(0, 0)));
+        assert_eq!(Some(Rgb::GREEN), c.colour(p(1, 0)));
+        assert_eq!(Some(Rgb::RED), c.colour(p(1, 1)));
+        assert_eq!(Some(Rgb::BLUE), c.colour(p(0, 1)));
+    }
+
+    #[test]
+    fn heat_map_spans_the_scale() {
+        let mut dist = Grid::filled(3, 2, None);
+        dist[p(1, 0)] = Some(4);
+        dist[p(1, 2)] = Some(8);
+        let c = canvas().heat_map(&dist);
+        assert_eq!(Some(Rgb(0, 0, 255)), c.colour(p(1, 0)));
+        assert_eq!(Some(Rgb(255, 0, 0)), c.colour(p(1, 2)));
+        assert_eq!(Some(Rgb::BLACK), c.colour(p(1, 1)));
+        assert_eq!(Rgb(0, 255, 0), Rgb::heat(0.5));
+    }
+
+    #[test]
+    fn output_formats() {
+        let c = canvas().pairs([(p(1, 0), p(1, 2))], Rgb::RED, Rgb::BLUE);
+
+        let ansi = c.to_ansi();
+        assert_eq!(2, ansi.lines().count());
+        assert!(ansi.contains("48;2;96;96;96m#"));
+
+        let ppm = c.to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(header, &ppm[..header.len()]);
+        assert_eq!(header.len() + 6 * 4 * 3, ppm.len());
+
+        let svg = c.to_svg(10);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(6, svg.matches("<rect").count());
+        assert!(svg.contains(r##"<line x1="5" y1="15" x2="25" y2="15" stroke="#dc2828""##));
+    }
+}
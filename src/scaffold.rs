@@ -0,0 +1,165 @@
+//! Creation of a new day from `templates/NN.rs`.
+
+use anyhow::*;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/NN.rs");
+
+/// Fills the template in for `day`: `NN` becomes the zero-padded day used in
+/// names and paths, `__DAY__` the plain day number.
+pub fn render(template: &str, day: u8) -> String {
+    template
+        .replace("__DAY__", &day.to_string())
+        .replace("NN", &format!("{:02}", day))
+}
+
+/// Adds `dayNN` to the module declarations and to `SOLUTIONS` in the source
+/// of `lib.rs`.
+pub fn register(lib_rs: &str, day: u8) -> Result<String> {
+    let module = format!("day{:02}", day);
+    let mod_line = format!("pub mod {};", module);
+    ensure!(
+        !lib_rs.lines().any(|l| l == mod_line),
+        "{} is already registered",
+        module
+    );
+
+    // keep the module declarations sorted
+    let mut lines = lib_rs.lines().map(str::to_string).collect::<Vec<_>>();
+    let mods = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("pub mod "))
+        .map(|(i, l)| (i, l.clone()))
+        .collect::<Vec<_>>();
+    let (last_mod, _) = *mods.last().context("no module declarations in lib.rs")?;
+    let at = mods
+        .iter()
+        .find(|(_, l)| *l > mod_line)
+        .map_or(last_mod + 1, |(i, _)| *i);
+    lines.insert(at, mod_line);
+    let lib_rs = lines.join("\n") + "\n";
+
+    let solutions = Regex::new(r"(?s)pub const SOLUTIONS: &\[&dyn Solution\] =\s*&\[(.*?)\];")?;
+    let entries = solutions
+        .captures(&lib_rs)
+        .context("no SOLUTIONS in lib.rs")?;
+    let mut days = Regex::new(r"&day(\d\d)::Day\d\d")?
+        .captures_iter(&entries[1])
+        .map(|c| c[1].parse::<u8>())
+        .collect::<Result<Vec<_>, _>>()?;
+    days.push(day);
+    days.sort_unstable();
+
+    let list = days
+        .iter()
+        .map(|d| format!("    &day{:02}::Day{:02},\n", d, d))
+        .collect::<String>();
+    let replacement = format!("pub const SOLUTIONS: &[&dyn Solution] = &[\n{}];", list);
+    Ok(solutions
+        .replace(&lib_rs, replacement.as_str())
+        .into_owned())
+}
+
+/// Creates the module, binary, input and example files of `day` below the
+/// crate root `root` and registers the module. Refuses to touch a day that
+/// already has any of these files.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    ensure!(
+        (1..=25).contains(&day),
+        "day must be between 1 and 25, got {}",
+        day
+    );
+
+    let source = root.join(format!("src/day{:02}.rs", day));
+    let binary = root.join(format!("src/bin/{:02}.rs", day));
+    let input = root.join(format!("input/{:02}.txt", day));
+    let example = root.join(format!("input/{:02}.example.txt", day));
+    let files = vec![source, binary, input, example];
+
+    if let Some(existing) = files.iter().find(|f| f.exists()) {
+        bail!("day {} already exists: {}", day, existing.display());
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let lib_rs = fs::read_to_string(&lib_path)
+        .with_context(|| format!("cannot read {}", lib_path.display()))?;
+    let lib_rs = register(&lib_rs, day)?;
+
+    fs::create_dir_all(root.join("src/bin"))?;
+    fs::create_dir_all(root.join("input"))?;
+    fs::write(&files[0], render(TEMPLATE, day))?;
+    fs::write(
+        &files[1],
+        format!(
+            "use adv_code_2024::*;\nuse anyhow::*;\n\n\
+             fn main() -> Result<()> {{\n    \
+             run_day(&day{0:02}::Day{0:02}, &InputSource::resolve({0}, None))?;\n    \
+             Ok(())\n}}\n",
+            day
+        ),
+    )?;
+    fs::write(&files[2], "")?;
+    fs::write(&files[3], "")?;
+    fs::write(&lib_path, lib_rs)?;
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB_RS: &str = "\
+pub mod answers;
+pub mod day01;
+pub mod day20;
+pub mod input;
+
+/// Every implemented day, in calendar order.
+pub const SOLUTIONS: &[&dyn Solution] =
+    &[&day01::Day01, &day20::Day20];
+";
+
+    #[test]
+    fn template_is_filled_in() {
+        let source = render(TEMPLATE, 7);
+        assert!(source.contains("pub struct Day07;"));
+        assert!(source.contains("        7\n"));
+        assert!(source.contains("input/07.example.txt"));
+        assert!(!source.contains("NN") && !source.contains("__DAY__"));
+    }
+
+    #[test]
+    fn day_is_registered_in_order() -> Result<()> {
+        let lib_rs = register(LIB_RS, 12)?;
+        assert!(lib_rs.contains("pub mod day01;\npub mod day12;\npub mod day20;\n"));
+        assert!(lib_rs.contains(
+            "pub const SOLUTIONS: &[&dyn Solution] = &[\n    \
+             &day01::Day01,\n    &day12::Day12,\n    &day20::Day20,\n];"
+        ));
+
+        assert!(register(&lib_rs, 12).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn existing_day_is_not_overwritten() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src"))?;
+        fs::write(root.join("src/lib.rs"), LIB_RS)?;
+
+        let created = new_day(&root, 3)?;
+        assert_eq!(4, created.len());
+        assert!(created.iter().all(|f| f.exists()));
+        assert!(fs::read_to_string(root.join("src/lib.rs"))?.contains("pub mod day03;"));
+
+        let err = new_day(&root, 3).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
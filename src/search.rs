@@ -0,0 +1,274 @@
+//! Shortest paths over arbitrary state spaces, with shortcuts for grids.
+//!
+//! The searches take the start state and a function listing the neighbours of
+//! a state, so they work for plain positions as well as for states carrying a
+//! direction or other extra data.
+
+use crate::grid::Grid;
+use crate::point::Point;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Distances from the start and the predecessor of every reached state.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S> {
+    pub dist: HashMap<S, usize>,
+    pub prev: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash> SearchResult<S> {
+    fn new(start: S) -> Self {
+        SearchResult {
+            dist: HashMap::from([(start, 0)]),
+            prev: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.dist.get(state).copied()
+    }
+
+    /// States from the start to `target`, both included, or `None` if
+    /// `target` was not reached.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        self.dist.get(target)?;
+        let mut path = vec![target.clone()];
+        while let Some(prev) = self.prev.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search from `start` where every step costs 1, visiting
+/// every reachable state.
+pub fn bfs<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let d = result.dist[&state];
+        for next in neighbours(&state) {
+            if !result.dist.contains_key(&next) {
+                result.dist.insert(next.clone(), d + 1);
+                result.prev.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's search from `start`, where `neighbours` yields the next states
+/// with the cost of the step to them, visiting every reachable state.
+pub fn dijkstra<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut result = SearchResult::new(start.clone());
+    let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+    while let Some(Reverse((d, state))) = heap.pop() {
+        if d > result.dist[&state] {
+            // outdated entry, the state was reached cheaper meanwhile
+            continue;
+        }
+        for (next, cost) in neighbours(&state) {
+            let nd = d + cost;
+            if result.dist.get(&next).is_none_or(|&old| nd < old) {
+                result.dist.insert(next.clone(), nd);
+                result.prev.insert(next.clone(), state.clone());
+                heap.push(Reverse((nd, next)));
+            }
+        }
+    }
+
+    result
+}
+
+/// A* search from `start` to the first state matching `goal`, returning that
+/// state and the same distances and predecessors as [`dijkstra`]. The search
+/// stops at the goal, so they only cover the states seen until then, and
+/// only the distances of the goal and the states on its path are final.
+/// `heuristic` must never overestimate the remaining cost.
+pub fn astar<S, I>(
+    start: S,
+    mut goal: impl FnMut(&S) -> bool,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+) -> Option<(S, SearchResult<S>)>
+where
+    S: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut result = SearchResult::new(start.clone());
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, start))]);
+
+    while let Some(Reverse((_, d, state))) = heap.pop() {
+        if d > result.dist[&state] {
+            continue;
+        }
+        if goal(&state) {
+            return Some((state, result));
+        }
+        for (next, cost) in neighbours(&state) {
+            let nd = d + cost;
+            if result.dist.get(&next).is_none_or(|&old| nd < old) {
+                result.dist.insert(next.clone(), nd);
+                result.prev.insert(next.clone(), state.clone());
+                heap.push(Reverse((nd + heuristic(&next), nd, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Breadth first search over the cells of `grid` that are `walkable`, moving
+/// between cells sharing an edge.
+pub fn bfs_grid<T>(
+    grid: &Grid<T>,
+    start: Point,
+    walkable: impl Fn(&T) -> bool,
+) -> SearchResult<Point> {
+    bfs(start, |&pos| {
+        grid.neighbours4(pos)
+            .filter(|&n| walkable(&grid[n]))
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Number of steps from `start` to every cell of `grid`, `None` for the cells
+/// that cannot be reached.
+pub fn distance_grid<T>(
+    grid: &Grid<T>,
+    start: Point,
+    walkable: impl Fn(&T) -> bool,
+) -> Grid<Option<usize>> {
+    let result = bfs_grid(grid, start, walkable);
+    let mut dist = Grid::filled(grid.width(), grid.height(), None);
+    for (&pos, &d) in &result.dist {
+        dist[pos] = Some(d);
+    }
+    dist
+}
+
+/// [`astar`] from `start` to `end` over the `walkable` cells of `grid`, with
+/// the Manhattan distance as heuristic, or `None` if `end` cannot be reached.
+pub fn astar_grid<T>(
+    grid: &Grid<T>,
+    start: Point,
+    end: Point,
+    walkable: impl Fn(&T) -> bool,
+) -> Option<SearchResult<Point>> {
+    let (_, result) = astar(
+        start,
+        |&pos| pos == end,
+        |&pos| {
+            grid.neighbours4(pos)
+                .filter(|&n| walkable(&grid[n]))
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        },
+        |&pos| pos.manhattan(end),
+    )?;
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+#######
+#S..#.#
+#.#.#.#
+#.#...#
+#...#E#
+#######
+";
+
+    fn p(row: usize, col: usize) -> Point {
+        Point::new(row, col)
+    }
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE.as_bytes(), Some).unwrap()
+    }
+
+    #[test]
+    fn bfs_on_grid() {
+        let g = maze();
+        let result = bfs_grid(&g, p(1, 1), |&c| c != '#');
+        assert_eq!(Some(7), result.distance(&p(4, 5)));
+        assert_eq!(Some(5), result.distance(&p(4, 3)));
+
+        let path = result.path_to(&p(4, 5)).unwrap();
+        assert_eq!(8, path.len());
+        assert_eq!(p(1, 1), path[0]);
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let dist = distance_grid(&g, p(1, 1), |&c| c != '#');
+        assert_eq!(None, dist[p(0, 0)]);
+        assert_eq!(Some(0), dist[p(1, 1)]);
+        assert_eq!(Some(7), dist[p(4, 5)]);
+    }
+
+    #[test]
+    fn unreachable_target() {
+        let g = maze();
+        let result = bfs_grid(&g, p(1, 1), |&c| c == '.' || c == 'S');
+        assert_eq!(None, result.path_to(&p(4, 5)));
+        assert!(astar_grid(&g, p(1, 1), p(4, 5), |&c| c == '.').is_none());
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_edges() {
+        // 0 -> 2 directly costs 10, through 1 only 3
+        let edges = |&s: &u8| match s {
+            0 => vec![(1, 1), (2, 10)],
+            1 => vec![(2, 2)],
+            _ => vec![],
+        };
+        let result = dijkstra(0u8, edges);
+        assert_eq!(Some(3), result.distance(&2));
+        assert_eq!(Some(vec![0, 1, 2]), result.path_to(&2));
+    }
+
+    #[test]
+    fn astar_matches_bfs() {
+        let g = maze();
+        let found = astar_grid(&g, p(1, 1), p(4, 5), |&c| c != '#').unwrap();
+        assert_eq!(Some(7), found.distance(&p(4, 5)));
+        let path = found.path_to(&p(4, 5)).unwrap();
+        assert_eq!((8, p(1, 1)), (path.len(), path[0]));
+
+        let result = bfs_grid(&g, p(1, 1), |&c| c != '#');
+        for (pos, _) in g.iter().filter(|(_, &c)| c != '#') {
+            let found = astar_grid(&g, p(1, 1), pos, |&c| c != '#').unwrap();
+            assert_eq!(result.distance(&pos), found.distance(&pos));
+        }
+    }
+
+    #[test]
+    fn astar_returns_the_goal_reached() {
+        // the first state of at least 3, the cheaper one through 1
+        let edges = |&s: &u8| match s {
+            0 => vec![(1, 1), (4, 10)],
+            1 => vec![(3, 2)],
+            _ => vec![],
+        };
+        let (goal, result) = astar(0u8, |&s| s >= 3, edges, |_| 0).unwrap();
+        assert_eq!(3, goal);
+        assert_eq!(Some(3), result.distance(&goal));
+        assert_eq!(Some(vec![0, 1, 3]), result.path_to(&goal));
+    }
+}
@@ -0,0 +1,174 @@
+//! Runs every registered day and collects the answers into one table.
+
+use crate::{solve, InputError, InputSource, Solution, SOLUTIONS};
+use std::fmt::Write;
+use std::io::ErrorKind;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Ok,
+    /// The puzzle input of the day does not exist.
+    Skipped,
+    Failed(String),
+}
+
+/// Outcome of running one part of one day.
+#[derive(Debug)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<usize>,
+    pub elapsed: Duration,
+    pub status: Status,
+}
+
+/// Runs both parts of every registered day on its puzzle input.
+pub fn run_all() -> Vec<PartReport> {
+    SOLUTIONS.iter().flat_map(|s| run_solution(*s)).collect()
+}
+
+/// Runs both parts of `solution`, reporting a missing input file as skipped
+/// and errors as failed instead of aborting.
+pub fn run_solution(solution: &dyn Solution) -> Vec<PartReport> {
+    let report = |part| PartReport {
+        day: solution.day(),
+        part,
+        answer: None,
+        elapsed: Duration::ZERO,
+        status: Status::Ok,
+    };
+
+    let source = InputSource::resolve(solution.day(), None);
+    let input = match source.load() {
+        Ok(input) => input,
+        Err(e) => {
+            let status = match e.kind() {
+                ErrorKind::NotFound => Status::Skipped,
+                _ => Status::Failed(format!("cannot read {}: {}", source.name(), e)),
+            };
+            return (1..=2)
+                .map(|part| PartReport {
+                    status: status.clone(),
+                    ..report(part)
+                })
+                .collect();
+        }
+    };
+
+    (1..=2)
+        .map(|part| {
+            let mut report = report(part);
+
+            let start = Instant::now();
+            let result = solve(solution, part, &mut input.reader())
+                .map_err(|e| InputError::attach_file(e, &input.name));
+            report.elapsed = start.elapsed();
+
+            match result {
+                Ok(answer) => report.answer = Some(answer),
+                Err(e) => report.status = Status::Failed(format!("{:#}", e)),
+            }
+            report
+        })
+        .collect()
+}
+
+/// Renders `reports` as a plain text table, one row per part.
+pub fn format_table(reports: &[PartReport]) -> String {
+    let answers = reports
+        .iter()
+        .map(|r| r.answer.map(|a| a.to_string()).unwrap_or_default())
+        .collect::<Vec<_>>();
+    let width = answers.iter().map(|a| a.len()).max().unwrap_or(0).max(6);
+
+    let mut table = String::new();
+    writeln!(
+        table,
+        "Day | Part | {:>width$} | {:>10} | Status",
+        "Answer", "Time"
+    )
+    .unwrap();
+    writeln!(
+        table,
+        "----+------+-{}-+------------+-------",
+        "-".repeat(width)
+    )
+    .unwrap();
+
+    for (report, answer) in reports.iter().zip(answers) {
+        let (time, status) = match &report.status {
+            Status::Ok => (format!("{:.2?}", report.elapsed), "ok".to_string()),
+            Status::Skipped => (String::new(), "skipped (no input)".to_string()),
+            Status::Failed(e) => (format!("{:.2?}", report.elapsed), format!("failed: {}", e)),
+        };
+        writeln!(
+            table,
+            " {:02} | {:>4} | {:>width$} | {:>10} | {}",
+            report.day, report.part, answer, time, status
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::*;
+    use std::io::BufRead;
+
+    struct Missing;
+
+    impl Solution for Missing {
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn part1(&self, _reader: &mut dyn BufRead) -> Result<usize> {
+            Ok(1)
+        }
+
+        fn part2(&self, _reader: &mut dyn BufRead) -> Result<usize> {
+            Ok(2)
+        }
+    }
+
+    #[test]
+    fn missing_input_is_skipped() {
+        let reports = run_solution(&Missing);
+        assert_eq!(2, reports.len());
+        assert!(reports.iter().all(|r| r.status == Status::Skipped));
+        assert!(reports.iter().all(|r| r.answer.is_none()));
+    }
+
+    #[test]
+    fn table_has_one_row_per_part() {
+        let reports = vec![
+            PartReport {
+                day: 1,
+                part: 1,
+                answer: Some(11),
+                elapsed: Duration::from_millis(2),
+                status: Status::Ok,
+            },
+            PartReport {
+                day: 25,
+                part: 2,
+                answer: None,
+                elapsed: Duration::ZERO,
+                status: Status::Skipped,
+            },
+        ];
+
+        let table = format_table(&reports);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(4, lines.len());
+        assert_eq!(" 01 |    1 |     11 |     2.00ms | ok", lines[2]);
+        assert_eq!(
+            " 25 |    2 |        |            | skipped (no input)",
+            lines[3]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::Solution;
+use anyhow::*;
+use std::io::BufRead;
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    fn day(&self) -> u8 {
+        __DAY__
+    }
+
+    fn part1(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part1(reader)
+    }
+
+    fn part2(&self, reader: &mut dyn BufRead) -> Result<usize> {
+        part2(reader)
+    }
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<usize> {
+    // TODO: Solve Part 1 of the puzzle
+    let answer = reader.lines().map_while(Result::ok).count();
+    Ok(answer)
+}
+
+pub fn part2<R: BufRead>(_reader: R) -> Result<usize> {
+    // TODO: Solve Part 2 of the puzzle
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TODO: Paste the example of the puzzle into this file
+    const TEST: &str = include_str!("../input/NN.example.txt");
+
+    // TODO: Set the expected answers for the example
+    crate::examples! {
+        part1_example: part1(TEST) => 0;
+        #[ignore = "part 2 is not solved yet"]
+        part2_example: part2(TEST) => 0;
+    }
+}